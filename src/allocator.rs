@@ -0,0 +1,166 @@
+use core::alloc::Layout;
+
+/// Allocator of the memory behind [RuneBuf](crate::RuneBuf),
+/// [RuneArena](crate::RuneArena) and therefore [Rune](crate::Rune),
+/// [Runes](crate::Runes) and [RuneParts](crate::RuneParts), such that
+/// inputs may be segmented into memory managed elsewhere, e.g.: a bump
+/// allocator per request, or have their bytes accounted for.
+///
+/// Layouts are always obtained from `mem::layout`, i.e.:
+/// arrays of at least one `u8`.
+///
+/// # Safety
+///
+/// Implementations must uphold the same contract as
+/// [GlobalAlloc](core::alloc::GlobalAlloc): `allocate` returns either
+/// null or a pointer to memory valid for `layout` which remains valid
+/// until passed to `deallocate` with that same `layout`.
+///
+/// # Examples
+///
+/// ```
+/// use std::alloc::Layout;
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use utf8_rune::{DefaultAllocator, RuneAllocator, Runes};
+///
+/// #[derive(Default)]
+/// struct Accounting {
+///     bytes: AtomicUsize,
+/// }
+///
+/// unsafe impl RuneAllocator for Accounting {
+///     fn allocate(&self, layout: Layout) -> *mut u8 {
+///         self.bytes.fetch_add(layout.size(), Ordering::Relaxed);
+///         DefaultAllocator.allocate(layout)
+///     }
+///
+///     unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+///         self.bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+///         unsafe { DefaultAllocator.deallocate(ptr, layout) }
+///     }
+/// }
+///
+/// let tenant = Arc::new(Accounting::default());
+/// let runes = Runes::allocate_in("👌🏽❤️‍🔥", tenant.clone()).unwrap();
+/// assert_eq!(tenant.bytes.load(Ordering::Relaxed), 21);
+///
+/// let clone = runes.clone();
+/// assert_eq!(tenant.bytes.load(Ordering::Relaxed), 42);
+/// drop(runes);
+/// drop(clone);
+/// assert_eq!(tenant.bytes.load(Ordering::Relaxed), 0);
+/// ```
+pub unsafe trait RuneAllocator: Send + Sync {
+    /// allocates memory for `layout`, returning null upon failure
+    fn allocate(&self, layout: Layout) -> *mut u8;
+
+    /// deallocates the memory at `ptr`
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [RuneAllocator::allocate] of
+    /// this same allocator with the given `layout`.
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout);
+}
+
+/// [RuneAllocator] used unless another one is given, which allocates
+/// zeroed memory from the global allocator
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DefaultAllocator;
+
+unsafe impl RuneAllocator for DefaultAllocator {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> *mut u8 {
+        unsafe { alloc::alloc::alloc_zeroed(layout) }
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { alloc::alloc::dealloc(ptr, layout) }
+    }
+}
+
+#[cfg(test)]
+mod test_allocator {
+    use std::alloc::Layout;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::{Error, Result, Rune, RuneAllocator, RuneParts};
+
+    /// bump allocator over a fixed region which never reuses memory
+    struct Bump {
+        region: *mut [u8],
+        offset: AtomicUsize,
+        live: AtomicUsize,
+    }
+
+    unsafe impl Send for Bump {}
+    unsafe impl Sync for Bump {}
+
+    impl Bump {
+        fn new(capacity: usize) -> Arc<Bump> {
+            Arc::new(Bump {
+                region: Box::into_raw(vec![0; capacity].into_boxed_slice()),
+                offset: AtomicUsize::new(0),
+                live: AtomicUsize::new(0),
+            })
+        }
+
+        fn contains(&self, ptr: *const u8) -> bool {
+            let start = self.region as *const u8;
+            (start..start.wrapping_add(self.region.len())).contains(&ptr)
+        }
+    }
+
+    impl Drop for Bump {
+        fn drop(&mut self) {
+            drop(unsafe { Box::from_raw(self.region) });
+        }
+    }
+
+    unsafe impl RuneAllocator for Bump {
+        fn allocate(&self, layout: Layout) -> *mut u8 {
+            let offset = self
+                .offset
+                .fetch_add(layout.size(), Ordering::Relaxed);
+            if offset + layout.size() > self.region.len() {
+                return std::ptr::null_mut();
+            }
+            self.live.fetch_add(1, Ordering::Relaxed);
+            unsafe { (self.region as *mut u8).add(offset) }
+        }
+
+        unsafe fn deallocate(&self, ptr: *mut u8, _layout: Layout) {
+            assert!(self.contains(ptr));
+            self.live.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_segment_into_bump_allocator() -> Result<()> {
+        let bump = Bump::new(56);
+        let parts = RuneParts::allocate_in("👨‍👩‍👧‍👦é!", bump.clone())?;
+        assert!(bump.contains(parts.as_bytes().as_ptr()));
+
+        let runes = parts.runes()?;
+        assert_eq!(runes.len(), 3);
+        assert!(runes
+            .iter()
+            .all(|rune| bump.contains(rune.as_ptr())));
+        assert_eq!(bump.live.load(Ordering::Relaxed), 1);
+
+        let rune = parts.rune_at_index(0)?;
+        assert!(bump.contains(rune.as_ptr()));
+        assert_eq!(rune, Rune::new("👨‍👩‍👧‍👦"));
+
+        drop((parts, runes, rune));
+        assert_eq!(bump.live.load(Ordering::Relaxed), 0);
+
+        let error = RuneParts::allocate_in("👨‍👩‍👧‍👦", bump.clone()).unwrap_err();
+        assert!(matches!(error, Error::MemoryError(_)), "{error:?}");
+        Ok(())
+    }
+}
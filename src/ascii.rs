@@ -0,0 +1,153 @@
+//! fast path over runs of ASCII bytes
+//!
+//! Between two ASCII bytes the only grapheme cluster boundary which
+//! does not occur is that of `CR LF`, such that every byte of a run of
+//! ASCII bytes but the last is a "[rune](crate::Rune)" of its own, or
+//! part of a `CR LF` rune, without consulting
+//! [GraphemeClusterState](crate::GraphemeClusterState).
+
+use alloc::vec::Vec;
+
+const WORD: usize = core::mem::size_of::<usize>();
+const HIGH_BITS: usize = usize::from_ne_bytes([0x80; WORD]);
+
+/// returns the number of ASCII bytes at the start of `bytes`.
+///
+/// Scans blocks of 16 bytes with SSE2 on `x86_64`, where it is always
+/// available, and word-sized blocks elsewhere.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::ascii_prefix_len;
+/// assert_eq!(ascii_prefix_len(b"plain ASCII"), 11);
+/// assert_eq!(ascii_prefix_len("ascii then 🦅".as_bytes()), 11);
+/// assert_eq!(ascii_prefix_len("é".as_bytes()), 0);
+/// ```
+#[inline]
+pub fn ascii_prefix_len(bytes: &[u8]) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    {
+        ascii_prefix_len_sse2(bytes)
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        ascii_prefix_len_swar(bytes)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[inline]
+fn ascii_prefix_len_sse2(bytes: &[u8]) -> usize {
+    use core::arch::x86_64::{__m128i, _mm_loadu_si128, _mm_movemask_epi8};
+
+    let mut index = 0;
+    while index + 16 <= bytes.len() {
+        // SAFETY: SSE2 is part of the x86_64 baseline and the 16 bytes
+        // from `index` are within `bytes`, unaligned loads allowed.
+        let mask = unsafe {
+            _mm_movemask_epi8(_mm_loadu_si128(
+                bytes.as_ptr().add(index) as *const __m128i
+            ))
+        };
+        if mask != 0 {
+            return index + mask.trailing_zeros() as usize;
+        }
+        index += 16;
+    }
+    index + ascii_prefix_len_swar(&bytes[index..])
+}
+
+/// portable equivalent of [ascii_prefix_len] which tests `usize`
+/// words at once for bytes with their most significant bit set
+#[inline]
+fn ascii_prefix_len_swar(bytes: &[u8]) -> usize {
+    let mut index = 0;
+    for chunk in bytes.chunks_exact(WORD) {
+        let word = usize::from_ne_bytes(chunk.try_into().expect("chunk of WORD bytes"));
+        if word & HIGH_BITS != 0 {
+            break;
+        }
+        index += WORD;
+    }
+    index
+        + bytes[index..]
+            .iter()
+            .take_while(|byte| byte.is_ascii())
+            .count()
+}
+
+/// pushes the cutoff of every "[rune](crate::Rune)" within the run of
+/// ASCII bytes at `cutoff` onto `indexes` and returns the cutoff from
+/// which the full grapheme cluster rules apply again.
+///
+/// The last byte of a run followed by non-ASCII bytes is left out since
+/// it may start a cluster along with combining marks.
+pub(crate) fn push_ascii_cutoffs(
+    bytes: &[u8],
+    mut cutoff: usize,
+    indexes: &mut Vec<usize>,
+) -> usize {
+    let run = cutoff + ascii_prefix_len(&bytes[cutoff..]);
+    let end = if run == bytes.len() {
+        run
+    } else {
+        run.saturating_sub(1).max(cutoff)
+    };
+    while cutoff < end {
+        cutoff += if bytes[cutoff] == b'\r' && bytes.get(cutoff + 1) == Some(&b'\n') {
+            2
+        } else {
+            1
+        };
+        indexes.push(cutoff);
+    }
+    cutoff
+}
+
+#[cfg(test)]
+mod test_ascii {
+    use super::{ascii_prefix_len_swar, push_ascii_cutoffs};
+    use crate::ascii_prefix_len;
+
+    #[test]
+    fn test_ascii_prefix_len_at_every_position() {
+        for length in 0..80 {
+            for position in 0..=length {
+                let mut bytes = vec![b'a'; length];
+                if position < length {
+                    bytes[position] = 0xC3;
+                }
+                assert_eq!(ascii_prefix_len(&bytes), position, "{length} {position}");
+                assert_eq!(
+                    ascii_prefix_len_swar(&bytes),
+                    position,
+                    "{length} {position}"
+                );
+                if position > 0 {
+                    assert_eq!(
+                        ascii_prefix_len(&bytes[1..]),
+                        position - 1,
+                        "unaligned {length} {position}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_ascii_cutoffs() {
+        let mut indexes = vec![0];
+        let bytes = "ab\r\ne\u{301}".as_bytes();
+        assert_eq!(push_ascii_cutoffs(bytes, 0, &mut indexes), 4);
+        assert_eq!(indexes, vec![0, 1, 2, 4]);
+
+        let mut indexes = vec![0];
+        assert_eq!(push_ascii_cutoffs(b"a\r\n", 0, &mut indexes), 3);
+        assert_eq!(indexes, vec![0, 1, 3]);
+
+        let mut indexes = vec![0];
+        assert_eq!(push_ascii_cutoffs("a🦅".as_bytes(), 0, &mut indexes), 0);
+        assert_eq!(indexes, vec![0]);
+    }
+}
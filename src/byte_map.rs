@@ -0,0 +1,337 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::ByteType;
+
+/// role of a byte within the UTF-8 sequence it belongs to, see
+/// [ByteMap](crate::ByteMap)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ByteRole {
+    Ascii,
+    Lead,
+    Continuation,
+    Invalid,
+}
+
+impl ByteRole {
+    const ALL: [ByteRole; 4] = [
+        ByteRole::Ascii,
+        ByteRole::Lead,
+        ByteRole::Continuation,
+        ByteRole::Invalid,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ByteRole::Ascii => "ascii",
+            ByteRole::Lead => "lead",
+            ByteRole::Continuation => "continuation",
+            ByteRole::Invalid => "invalid",
+        }
+    }
+}
+
+/// classification of a single byte of a [ByteMap](crate::ByteMap)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ByteInfo {
+    pub byte: u8,
+    pub role: ByteRole,
+    /// length of the sequence the byte belongs to, that is the
+    /// maximal invalid subpart for [ByteRole::Invalid](crate::ByteRole::Invalid)
+    pub len: usize,
+    /// index of the code point the byte belongs to, each maximal
+    /// invalid subpart counting as one code point as per
+    /// [String::from_utf8_lossy]
+    pub code_point: usize,
+}
+
+impl ByteInfo {
+    pub fn byte_type(&self) -> ByteType {
+        ByteType::from(self.byte)
+    }
+}
+
+/// Classifies every byte of a slice according to the UTF-8 sequence
+/// it belongs to, such that inputs can be inspected byte by byte and
+/// split without breaking code points.
+///
+/// Each byte takes a single byte of the map, which also tells whether
+/// a code point starts at it, along with a running count of code
+/// points every 64 bytes from which code point indexes are looked
+/// up, i.e.: about 1.125 bytes per byte of input.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::{ByteMap, ByteRole};
+///
+/// let map = ByteMap::new(b"e\xCC\x81\xFF!");
+/// assert_eq!(map.len(), 5);
+/// assert_eq!(map.code_point_count(), 4);
+///
+/// let info = map.get(2).unwrap();
+/// assert_eq!(info.role, ByteRole::Continuation);
+/// assert_eq!(info.len, 2);
+/// assert_eq!(info.code_point, 1);
+/// assert_eq!(map.get(3).unwrap().role, ByteRole::Invalid);
+///
+/// assert_eq!(map.is_boundary(2), false);
+/// assert_eq!(map.floor_boundary(2), 1);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ByteMap<'g> {
+    bytes: &'g [u8],
+    classes: Vec<u8>,
+    /// number of code points starting before each block of [BLOCK]
+    /// bytes
+    counts: Vec<usize>,
+    code_point_count: usize,
+}
+
+/// number of bytes of a [ByteMap] per running count of code points
+const BLOCK: usize = 64;
+
+/// bit of a packed class set on the first byte of a code point
+const START: u8 = 0x80;
+
+impl<'g> ByteMap<'g> {
+    pub fn new(bytes: &'g [u8]) -> ByteMap<'g> {
+        let mut classes = Vec::<u8>::with_capacity(bytes.len());
+        for chunk in bytes.utf8_chunks() {
+            for c in chunk.valid().chars() {
+                let len = c.len_utf8();
+                if len == 1 {
+                    classes.push(START | pack(ByteRole::Ascii, 1));
+                } else {
+                    classes.push(START | pack(ByteRole::Lead, len));
+                    classes.extend(core::iter::repeat_n(
+                        pack(ByteRole::Continuation, len),
+                        len - 1,
+                    ));
+                }
+            }
+            let invalid = chunk.invalid();
+            if !invalid.is_empty() {
+                classes.push(START | pack(ByteRole::Invalid, invalid.len()));
+                classes.extend(core::iter::repeat_n(
+                    pack(ByteRole::Invalid, invalid.len()),
+                    invalid.len() - 1,
+                ));
+            }
+        }
+        let mut counts = Vec::<usize>::with_capacity(classes.len().div_ceil(BLOCK));
+        let mut code_point_count = 0;
+        for block in classes.chunks(BLOCK) {
+            counts.push(code_point_count);
+            code_point_count += count_starts(block);
+        }
+        ByteMap {
+            bytes,
+            classes,
+            counts,
+            code_point_count,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn as_bytes(&self) -> &'g [u8] {
+        self.bytes
+    }
+
+    /// number of code points, counting each maximal invalid subpart
+    /// as one
+    pub fn code_point_count(&self) -> usize {
+        self.code_point_count
+    }
+
+    pub fn get(&self, index: usize) -> Option<ByteInfo> {
+        let class = *self.classes.get(index)?;
+        let block = index / BLOCK;
+        let starts = count_starts(&self.classes[block * BLOCK..=index]);
+        Some(ByteInfo {
+            byte: self.bytes[index],
+            role: ByteRole::ALL[((class & !START) >> 4) as usize],
+            len: (class & 0x0F) as usize,
+            code_point: self.counts[block] + starts - 1,
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ByteInfo> + '_ {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+
+    /// returns `true` if splitting the input at `index` keeps every
+    /// code point and every maximal invalid subpart whole
+    pub fn is_boundary(&self, index: usize) -> bool {
+        match self.classes.get(index) {
+            Some(class) => class & START != 0,
+            None => index == self.len(),
+        }
+    }
+
+    /// greatest [boundary](ByteMap::is_boundary) not greater than
+    /// `index`
+    pub fn floor_boundary(&self, index: usize) -> usize {
+        if index >= self.len() {
+            return self.len();
+        }
+        // the first byte is always a boundary and no code point or
+        // maximal invalid subpart is longer than 4 bytes
+        let mut index = index;
+        while !self.is_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+}
+
+/// number of bytes of `classes` at which a code point starts
+fn count_starts(classes: &[u8]) -> usize {
+    classes
+        .iter()
+        .filter(|class| **class & START != 0)
+        .count()
+}
+
+/// packs a [ByteRole] in bits 4 and 5 and a length in the low nibble,
+/// leaving the [START] bit clear
+fn pack(role: ByteRole, len: usize) -> u8 {
+    ((role as u8) << 4) | (len as u8 & 0x0F)
+}
+
+impl core::fmt::Debug for ByteMap<'_> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "ByteMap{{bytes: {:02x?}}}", self.bytes)
+    }
+}
+
+/// renders an annotated table of the bytes of the map
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::ByteMap;
+/// assert_eq!(ByteMap::new("é\x7F".as_bytes()).to_string(), [
+///     "offset  byte  bits        type          role          len  code point  char",
+///     "     0  0xc3  0b11000011  Two           lead            2           0  'é'",
+///     "     1  0xa9  0b10101001  Continuation  continuation    2           0",
+///     "     2  0x7f  0b01111111  Ascii         ascii           1           1  '\\u{7f}'",
+/// ].join("\n"));
+/// ```
+impl Display for ByteMap<'_> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{:>6}  {:<4}  {:<10}  {:<12}  {:<12}  {:>3}  {:>10}  char",
+            "offset", "byte", "bits", "type", "role", "len", "code point"
+        )?;
+        for (index, info) in self.iter().enumerate() {
+            let c = match info.role {
+                ByteRole::Ascii | ByteRole::Lead =>
+                    core::str::from_utf8(&self.bytes[index..index + info.len])
+                        .ok()
+                        .and_then(|s| s.chars().next())
+                        .map(|c| format!("  {c:?}"))
+                        .unwrap_or_default(),
+                _ => String::new(),
+            };
+            write!(
+                f,
+                "\n{:>6}  0x{:02x}  0b{:08b}  {:<12}  {:<12}  {:>3}  {:>10}{c}",
+                index,
+                info.byte,
+                info.byte,
+                info.byte_type().name(),
+                info.role.name(),
+                info.len,
+                info.code_point,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_byte_map {
+    use crate::{ByteMap, ByteRole};
+
+    #[test]
+    fn test_roles_lengths_and_code_points() {
+        let bytes = b"a\xF0\x9F\xA6\x85\xE2\x80z\x80";
+        let map = ByteMap::new(bytes);
+        let classes = map
+            .iter()
+            .map(|info| (info.role, info.len, info.code_point))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            classes,
+            vec![
+                (ByteRole::Ascii, 1, 0),
+                (ByteRole::Lead, 4, 1),
+                (ByteRole::Continuation, 4, 1),
+                (ByteRole::Continuation, 4, 1),
+                (ByteRole::Continuation, 4, 1),
+                (ByteRole::Invalid, 2, 2),
+                (ByteRole::Invalid, 2, 2),
+                (ByteRole::Ascii, 1, 3),
+                (ByteRole::Invalid, 1, 4),
+            ]
+        );
+        assert_eq!(
+            map.code_point_count(),
+            String::from_utf8_lossy(bytes).chars().count()
+        );
+        assert_eq!(map.get(bytes.len()), None);
+    }
+
+    #[test]
+    fn test_code_points_across_blocks() {
+        let input = "é🦅a\u{301}".repeat(40);
+        let map = ByteMap::new(input.as_bytes());
+        let mut code_points = Vec::<usize>::new();
+        for (code_point, c) in input.chars().enumerate() {
+            code_points.extend(core::iter::repeat_n(code_point, c.len_utf8()));
+        }
+        assert_eq!(
+            map.iter()
+                .map(|info| info.code_point)
+                .collect::<Vec<_>>(),
+            code_points
+        );
+        assert_eq!(map.code_point_count(), input.chars().count());
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let input = "a🦅é";
+        let map = ByteMap::new(input.as_bytes());
+        for index in 0..=input.len() {
+            assert_eq!(
+                map.is_boundary(index),
+                input.is_char_boundary(index),
+                "{index}"
+            );
+            let floor = map.floor_boundary(index);
+            assert!(floor <= index && input.is_char_boundary(floor), "{index}");
+            assert!((floor + 1..=index).all(|i| !input.is_char_boundary(i)), "{index}");
+        }
+        assert_eq!(map.floor_boundary(100), input.len());
+
+        let map = ByteMap::new(b"\xE2\x80!");
+        assert!(!map.is_boundary(1));
+        assert_eq!(map.floor_boundary(1), 0);
+
+        let map = ByteMap::new(b"");
+        assert_eq!(map.floor_boundary(0), 0);
+        assert!(map.is_boundary(0));
+    }
+}
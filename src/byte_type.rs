@@ -1,8 +1,14 @@
-use std::fmt::{Debug, Formatter};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
 
 /// Represents UTF-8 byte type based on the most significant bits
 /// the given byte
 ///
+/// Bytes which never occur in valid UTF-8, i.e.: `0xC0`, `0xC1` and
+/// `0xF5..=0xFF`, are classified as [Invalid](ByteType::Invalid).
+///
 /// Examples
 ///
 /// ```
@@ -49,32 +55,43 @@ use std::fmt::{Debug, Formatter};
 /// assert_eq!(g.is_ascii(), false);
 /// assert_eq!(g.is_continuation(), true);
 /// ```
+///
+/// ```
+/// use utf8_rune::ByteType;
+/// assert_eq!(ByteType::from(0x7Fu8), ByteType::Ascii(0x7F));
+/// assert_eq!(ByteType::from(0xC1u8), ByteType::Invalid(0xC1));
+/// assert_eq!(ByteType::from(0xF8u8), ByteType::Invalid(0xF8));
+/// assert_eq!(ByteType::from(0xF8u8).len(), 1);
+/// assert_eq!(ByteType::from(0xF8u8).is_invalid(), true);
+/// ```
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum ByteType {
     None,
     Ascii(u8),
+    /// never produced by [ByteType::new], `0x7F` is [Ascii](ByteType::Ascii)
+    #[deprecated(note = "0x7F is classified as ByteType::Ascii")]
     One(u8),
     Two(u8),
     Three(u8),
+    /// lead byte of a four byte sequence, i.e.: `0xF0..=0xF4`
     FourOrMore(u8),
     Continuation(u8),
+    Invalid(u8),
 }
 
 impl ByteType {
     pub fn new(byte: u8) -> ByteType {
-        if byte < 127 {
-            ByteType::Ascii(byte)
-        } else {
-            match byte.leading_ones() {
-                0 => ByteType::One(byte),
-                1 => ByteType::Continuation(byte),
-                2 => ByteType::Two(byte),
-                3 => ByteType::Three(byte),
-                _ => ByteType::FourOrMore(byte),
-            }
+        match byte {
+            0x00..=0x7F => ByteType::Ascii(byte),
+            0x80..=0xBF => ByteType::Continuation(byte),
+            0xC2..=0xDF => ByteType::Two(byte),
+            0xE0..=0xEF => ByteType::Three(byte),
+            0xF0..=0xF4 => ByteType::FourOrMore(byte),
+            0xC0 | 0xC1 | 0xF5..=0xFF => ByteType::Invalid(byte),
         }
     }
 
+    #[allow(deprecated)]
     pub fn name(&self) -> &'static str {
         match self {
             ByteType::None => "None",
@@ -84,9 +101,11 @@ impl ByteType {
             ByteType::Three(_) => "Three",
             ByteType::FourOrMore(_) => "FourOrMore",
             ByteType::Continuation(_) => "Continuation",
+            ByteType::Invalid(_) => "Invalid",
         }
     }
 
+    #[allow(deprecated)]
     pub fn byte(&self) -> u8 {
         match self {
             ByteType::None => u8::default(),
@@ -96,37 +115,33 @@ impl ByteType {
             ByteType::Three(byte) => *byte,
             ByteType::FourOrMore(byte) => *byte,
             ByteType::Continuation(byte) => *byte,
+            ByteType::Invalid(byte) => *byte,
         }
     }
 
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         match self {
-            ByteType::Continuation(_) | ByteType::Ascii(_) => 1,
+            ByteType::None => 0,
+            ByteType::Continuation(_) | ByteType::Ascii(_) | ByteType::Invalid(_) => 1,
             _ => (self.byte().leading_ones()) as usize,
         }
     }
 
     pub fn is_ascii(&self) -> bool {
-        match self {
-            ByteType::Ascii(_) => true,
-            _ => false,
-        }
+        matches!(self, ByteType::Ascii(_))
     }
 
     pub fn is_continuation(&self) -> bool {
-        match self {
-            ByteType::Continuation(_) => true,
-            _ => false,
-        }
+        matches!(self, ByteType::Continuation(_))
+    }
+
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, ByteType::Invalid(_))
     }
 
     pub fn has_rune_delta(&self) -> bool {
-        match self {
-            ByteType::None => false,
-            ByteType::Ascii(_) => false,
-            ByteType::Continuation(_) => false,
-            _ => true,
-        }
+        matches!(self, ByteType::Two(_) | ByteType::Three(_) | ByteType::FourOrMore(_))
     }
 
     fn as_debug(&self, indent: Option<usize>) -> String {
@@ -139,7 +154,7 @@ impl ByteType {
                 format!(
                     "byte: 0x{:02x},{}",
                     self.byte(),
-                    if let Ok(c) = std::str::from_utf8(&[self.byte()]) {
+                    if let Ok(c) = core::str::from_utf8(&[self.byte()]) {
                         format!(" // \"{c}\"")
                     } else {
                         String::new()
@@ -167,29 +182,32 @@ impl From<&u8> for ByteType {
         ByteType::new(*byte)
     }
 }
+/// classifies only the least significant byte of `bytes`
 impl From<u16> for ByteType {
     fn from(bytes: u16) -> ByteType {
         ByteType::from(bytes.to_le_bytes()[0])
     }
 }
+/// classifies only the least significant byte of `bytes`
 impl From<u32> for ByteType {
     fn from(bytes: u32) -> ByteType {
         ByteType::from(bytes.to_le_bytes()[0])
     }
 }
+/// classifies only the least significant byte of `bytes`
 impl From<u64> for ByteType {
     fn from(bytes: u64) -> ByteType {
         ByteType::from(bytes.to_le_bytes()[0])
     }
 }
+/// classifies only the least significant byte of `bytes`
 impl From<usize> for ByteType {
     fn from(bytes: usize) -> ByteType {
         ByteType::from(bytes.to_le_bytes()[0])
     }
 }
-
 impl Debug for ByteType {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}", self.as_debug(None))
     }
 }
@@ -224,4 +242,43 @@ mod test_byte_type {
         let ob8f = ByteType::from(0b10001111u8);
         assert_eq!(ob8f.len(), 1);
     }
+
+    #[test]
+    fn test_byte_type_of_every_byte() {
+        assert_eq!(ByteType::from(0x7Fu8), ByteType::Ascii(0x7F));
+        for byte in [
+            0xC0u8, 0xC1, 0xF5, 0xF7, 0xF8, 0xFB, 0xFC, 0xFE, 0xFF,
+        ] {
+            assert_eq!(ByteType::from(byte), ByteType::Invalid(byte));
+        }
+        let mut seen = [false; 256];
+        for c in (0..=0x10FFFFu32).filter_map(char::from_u32) {
+            let mut buffer = [0u8; 4];
+            let bytes = c.encode_utf8(&mut buffer).as_bytes();
+            assert_eq!(ByteType::from(bytes[0]).len(), bytes.len(), "{c:?}");
+            for byte in bytes.iter().skip(1) {
+                assert!(ByteType::from(byte).is_continuation(), "{c:?}");
+            }
+            for byte in bytes {
+                seen[*byte as usize] = true;
+            }
+        }
+        for byte in 0..=0xFFu8 {
+            assert_eq!(ByteType::from(byte).is_invalid(), !seen[byte as usize]);
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_byte_type_legacy_variants() {
+        assert_eq!(ByteType::from(0xF4u8), ByteType::FourOrMore(0xF4));
+        assert_eq!(ByteType::FourOrMore(0xF4).name(), "FourOrMore");
+        assert_eq!(ByteType::One(0x7F).name(), "One");
+        assert_eq!(ByteType::One(0x7F).byte(), 0x7F);
+        assert_ne!(ByteType::from(0x7Fu8), ByteType::One(0x7F));
+        assert_eq!(ByteType::from(0x01F0u16), ByteType::FourOrMore(0xF0));
+        assert_eq!(ByteType::from(0xC3u32), ByteType::Two(0xC3));
+        assert_eq!(ByteType::from(0xFF80u64), ByteType::Continuation(0x80));
+        assert_eq!(ByteType::from(0x67usize), ByteType::Ascii(b'g'));
+    }
 }
@@ -1,18 +1,187 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
 use crate::mem::MemoryError;
+use crate::Excerpt;
 
+/// Represents the errors of this crate.
+///
+/// Errors own their data, such that they are `Send + Sync + 'static`,
+/// and carry the byte offset at which they occurred along with an
+/// [Excerpt](crate::Excerpt) of the input around that offset.
+///
+/// Invalid UTF-8 is reported as one of the following variants of
+/// `(offset, expected, actual, excerpt)` where `expected` is the
+/// number of bytes announced by the lead byte and `actual` the number
+/// of bytes of the offending sequence, see
+/// [byte_counts](Error::byte_counts):
+///
+/// - [TruncatedSequence](Error::TruncatedSequence): fewer continuation
+///   bytes than announced, either at the end of the input or followed
+///   by a byte which cannot continue the sequence
+/// - [OverlongEncoding](Error::OverlongEncoding): `C0`, `C1`, `E0 80..9F`
+///   and `F0 80..8F`
+/// - [EncodedSurrogate](Error::EncodedSurrogate): `ED A0..BF`
+/// - [CodePointTooLarge](Error::CodePointTooLarge): `F4 90..BF` and
+///   `F5..F7`
+/// - [InvalidLeadByte](Error::InvalidLeadByte): `F8..FF`, which
+///   announce no sequence such that `expected` is 0
+///
+/// A continuation byte `80..BF` where a sequence should start is
+/// reported as [UnexpectedContinuationByte](Error::UnexpectedContinuationByte)
+/// along with the valid cutoffs surrounding it, which are `None` when
+/// the input before and after it is not at hand, e.g.: within
+/// [Segmenter](crate::Segmenter).
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::{get_rune_cutoff_at_index, ErrorKind};
+///
+/// let bytes = [b'o', b'k', 0x80, b'!'];
+/// let error = get_rune_cutoff_at_index(bytes.as_ptr(), bytes.len(), 2).unwrap_err();
+/// assert_eq!(error.kind(), ErrorKind::UnexpectedContinuationByte);
+/// assert_eq!(error.offset(), Some(2));
+/// assert_eq!(error.excerpt().unwrap().as_bytes(), &bytes);
+///
+/// let boxed: Box<dyn std::error::Error + Send + Sync + 'static> = Box::new(error);
+/// ```
+///
+/// Error is `#[non_exhaustive]` as some of its variants depend on
+/// features, e.g.: `IoError` on `std`, such that matching on it
+/// requires a wildcard arm.
 #[derive(Clone, PartialEq, Eq)]
-pub enum Error<'g> {
-    InvalidIndex(usize, &'g [u8]),
-    UnexpectedContinuationByte(u8, usize, Option<usize>, Option<usize>, &'g [u8]),
-    Utf8Error(usize, &'g [u8], String),
+#[non_exhaustive]
+pub enum Error {
+    InvalidIndex(usize, Excerpt),
+    UnexpectedContinuationByte(u8, usize, Option<usize>, Option<usize>, Excerpt),
+    TruncatedSequence(usize, usize, usize, Excerpt),
+    OverlongEncoding(usize, usize, usize, Excerpt),
+    EncodedSurrogate(usize, usize, usize, Excerpt),
+    CodePointTooLarge(usize, usize, usize, Excerpt),
+    InvalidLeadByte(usize, usize, usize, Excerpt),
+    ClusterTooLong(usize, usize, Excerpt),
+    #[cfg(feature = "std")]
+    IoError(usize, std::io::ErrorKind, String),
     MemoryError(MemoryError),
 }
-impl<'g> Error<'g> {
+
+/// machine-readable kind of an [Error](crate::Error)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    InvalidIndex,
+    UnexpectedContinuationByte,
+    TruncatedSequence,
+    OverlongEncoding,
+    EncodedSurrogate,
+    CodePointTooLarge,
+    InvalidLeadByte,
+    ClusterTooLong,
+    IoError,
+    MemoryError,
+}
+
+impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::InvalidIndex(_, _) => ErrorKind::InvalidIndex,
+            Error::UnexpectedContinuationByte(_, _, _, _, _) =>
+                ErrorKind::UnexpectedContinuationByte,
+            Error::TruncatedSequence(_, _, _, _) => ErrorKind::TruncatedSequence,
+            Error::OverlongEncoding(_, _, _, _) => ErrorKind::OverlongEncoding,
+            Error::EncodedSurrogate(_, _, _, _) => ErrorKind::EncodedSurrogate,
+            Error::CodePointTooLarge(_, _, _, _) => ErrorKind::CodePointTooLarge,
+            Error::InvalidLeadByte(_, _, _, _) => ErrorKind::InvalidLeadByte,
+            Error::ClusterTooLong(_, _, _) => ErrorKind::ClusterTooLong,
+            #[cfg(feature = "std")]
+            Error::IoError(_, _, _) => ErrorKind::IoError,
+            Error::MemoryError(_) => ErrorKind::MemoryError,
+        }
+    }
+
+    /// byte offset within the input at which the error occurred
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            Error::InvalidIndex(index, _) => Some(*index),
+            Error::UnexpectedContinuationByte(_, index, _, _, _) => Some(*index),
+            Error::TruncatedSequence(index, _, _, _)
+            | Error::OverlongEncoding(index, _, _, _)
+            | Error::EncodedSurrogate(index, _, _, _)
+            | Error::CodePointTooLarge(index, _, _, _)
+            | Error::InvalidLeadByte(index, _, _, _) => Some(*index),
+            Error::ClusterTooLong(index, _, _) => Some(*index),
+            #[cfg(feature = "std")]
+            Error::IoError(offset, _, _) => Some(*offset),
+            Error::MemoryError(_) => None,
+        }
+    }
+
+    /// bytes of the input surrounding [offset](Error::offset)
+    pub fn excerpt(&self) -> Option<&Excerpt> {
+        match self {
+            Error::InvalidIndex(_, excerpt) => Some(excerpt),
+            Error::UnexpectedContinuationByte(_, _, _, _, excerpt) => Some(excerpt),
+            Error::TruncatedSequence(_, _, _, excerpt)
+            | Error::OverlongEncoding(_, _, _, excerpt)
+            | Error::EncodedSurrogate(_, _, _, excerpt)
+            | Error::CodePointTooLarge(_, _, _, excerpt)
+            | Error::InvalidLeadByte(_, _, _, excerpt) => Some(excerpt),
+            Error::ClusterTooLong(_, _, excerpt) => Some(excerpt),
+            #[cfg(feature = "std")]
+            Error::IoError(_, _, _) => None,
+            Error::MemoryError(_) => None,
+        }
+    }
+
+    /// number of bytes announced by the lead byte of an invalid UTF-8
+    /// sequence along with the number of bytes actually found in the
+    /// input before the sequence turned out to be invalid, i.e.:
+    /// the invalid bytes span `offset..offset + actual`.
+    pub fn byte_counts(&self) -> Option<(usize, usize)> {
+        match self {
+            Error::TruncatedSequence(_, expected, actual, _)
+            | Error::OverlongEncoding(_, expected, actual, _)
+            | Error::EncodedSurrogate(_, expected, actual, _)
+            | Error::CodePointTooLarge(_, expected, actual, _)
+            | Error::InvalidLeadByte(_, expected, actual, _) =>
+                Some((*expected, *actual)),
+            _ => None,
+        }
+    }
+
+    /// drops the bytes of the input held by the
+    /// [excerpt](Error::excerpt) of this error, such that it may be
+    /// reported without disclosing that input
+    pub(crate) fn redacted(mut self) -> Error {
+        match &mut self {
+            Error::InvalidIndex(_, excerpt)
+            | Error::UnexpectedContinuationByte(_, _, _, _, excerpt)
+            | Error::TruncatedSequence(_, _, _, excerpt)
+            | Error::OverlongEncoding(_, _, _, excerpt)
+            | Error::EncodedSurrogate(_, _, _, excerpt)
+            | Error::CodePointTooLarge(_, _, _, excerpt)
+            | Error::InvalidLeadByte(_, _, _, excerpt)
+            | Error::ClusterTooLong(_, _, excerpt) => excerpt.redact(),
+            #[cfg(feature = "std")]
+            Error::IoError(_, _, _) => {},
+            Error::MemoryError(_) => {},
+        }
+        self
+    }
+
     pub fn previous_valid_cutoff(&self) -> Option<usize> {
         match self {
             Error::InvalidIndex(_, _) => None,
-            Error::UnexpectedContinuationByte(_, _, previous, _, _) => previous.clone(),
-            Error::Utf8Error(_, _, _) => None,
+            Error::UnexpectedContinuationByte(_, _, previous, _, _) => *previous,
+            Error::TruncatedSequence(_, _, _, _)
+            | Error::OverlongEncoding(_, _, _, _)
+            | Error::EncodedSurrogate(_, _, _, _)
+            | Error::CodePointTooLarge(_, _, _, _)
+            | Error::InvalidLeadByte(_, _, _, _) => None,
+            Error::ClusterTooLong(_, _, _) => None,
+            #[cfg(feature = "std")]
+            Error::IoError(_, _, _) => None,
             Error::MemoryError(_) => None,
         }
     }
@@ -20,20 +189,29 @@ impl<'g> Error<'g> {
     pub fn next_valid_cutoff(&self) -> Option<usize> {
         match self {
             Error::InvalidIndex(_, _) => None,
-            Error::UnexpectedContinuationByte(_, _, _, next, _) => next.clone(),
-            Error::Utf8Error(_, _, _) => None,
+            Error::UnexpectedContinuationByte(_, _, _, next, _) => *next,
+            Error::TruncatedSequence(_, _, _, _)
+            | Error::OverlongEncoding(_, _, _, _)
+            | Error::EncodedSurrogate(_, _, _, _)
+            | Error::CodePointTooLarge(_, _, _, _)
+            | Error::InvalidLeadByte(_, _, _, _) => None,
+            Error::ClusterTooLong(_, _, _) => None,
+            #[cfg(feature = "std")]
+            Error::IoError(_, _, _) => None,
             Error::MemoryError(_) => None,
         }
     }
 }
-impl<'g> std::fmt::Display for Error<'g> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        fn format_slice(slice: &[u8], index: usize) -> String {
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fn format_slice(excerpt: &Excerpt, index: usize) -> String {
+            let slice = excerpt.as_bytes();
+            let index = index.wrapping_sub(excerpt.offset());
             [
                 format!(
                     "[{}]",
                     slice
-                        .into_iter()
+                        .iter()
                         .enumerate()
                         .map(|(i, byte)| if i == index {
                             format!("\x1b[1;38;5;220m0x{byte:02x}\x1b[0m")
@@ -55,18 +233,52 @@ impl<'g> std::fmt::Display for Error<'g> {
             "{}",
             match self {
                 Error::InvalidIndex(index, slice) => {
-                    let length = slice.len();
+                    let length = slice.end();
                     format!(
                         "invalid index {index}: {index} > {length} in {}",
                         format_slice(slice, *index)
                     )
                 },
-                Error::Utf8Error(index, slice, error) => {
+                Error::TruncatedSequence(index, expected, actual, slice) => {
+                    format!(
+                        "truncated UTF-8 sequence at index {index} of {}: expected {expected} bytes but found {actual}",
+                        format_slice(slice, *index)
+                    )
+                },
+                Error::OverlongEncoding(index, expected, actual, slice) => {
                     format!(
-                        "Utf8Error in index {index} of {}: {error}",
+                        "overlong UTF-8 encoding at index {index} of {}: {expected}-byte sequence invalid after {actual} byte(s)",
                         format_slice(slice, *index)
                     )
                 },
+                Error::EncodedSurrogate(index, expected, actual, slice) => {
+                    format!(
+                        "UTF-8 encoded surrogate at index {index} of {}: {expected}-byte sequence invalid after {actual} byte(s)",
+                        format_slice(slice, *index)
+                    )
+                },
+                Error::CodePointTooLarge(index, expected, actual, slice) => {
+                    format!(
+                        "UTF-8 sequence above U+10FFFF at index {index} of {}: {expected}-byte sequence invalid after {actual} byte(s)",
+                        format_slice(slice, *index)
+                    )
+                },
+                Error::InvalidLeadByte(index, expected, actual, slice) => {
+                    format!(
+                        "invalid UTF-8 lead byte at index {index} of {}: starts no sequence ({expected} expected bytes, {actual} found)",
+                        format_slice(slice, *index)
+                    )
+                },
+                Error::ClusterTooLong(index, max_trailing, slice) => {
+                    format!(
+                        "rune at index {index} has more than {max_trailing} code points after its first code point in {}",
+                        format_slice(slice, *index)
+                    )
+                },
+                #[cfg(feature = "std")]
+                Error::IoError(offset, _, error) => {
+                    format!("IoError after byte offset {offset}: {error}")
+                },
                 Error::MemoryError(error) => {
                     format!("{:#?}", error)
                 },
@@ -89,15 +301,15 @@ impl<'g> std::fmt::Display for Error<'g> {
         )
     }
 }
-impl<'g> std::fmt::Debug for Error<'g> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Debug for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{self}")
     }
 }
-impl<'g> std::error::Error for Error<'g> {}
-impl<'g> From<MemoryError> for Error<'g> {
-    fn from(e: MemoryError) -> Error<'g> {
+impl core::error::Error for Error {}
+impl From<MemoryError> for Error {
+    fn from(e: MemoryError) -> Error {
         Error::MemoryError(e)
     }
 }
-pub type Result<T> = std::result::Result<T, Error<'static>>;
+pub type Result<T> = core::result::Result<T, Error>;
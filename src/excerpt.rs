@@ -0,0 +1,87 @@
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+
+/// maximum number of bytes before and after the offset of an
+/// [Error](crate::Error) copied into its [Excerpt]
+pub const EXCERPT_RADIUS: usize = 16;
+
+/// Owned copy of at most `2 * EXCERPT_RADIUS` bytes of an input
+/// surrounding the offset at which an [Error](crate::Error) occurred.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::{Excerpt, EXCERPT_RADIUS};
+///
+/// let input = [b'x'; 100];
+/// let excerpt = Excerpt::of(&input, 50);
+/// assert_eq!(excerpt.offset(), 50 - EXCERPT_RADIUS);
+/// assert_eq!(excerpt.len(), 2 * EXCERPT_RADIUS);
+/// assert_eq!(excerpt.end(), 50 + EXCERPT_RADIUS);
+///
+/// let excerpt = Excerpt::of(b"short", 1);
+/// assert_eq!(excerpt.offset(), 0);
+/// assert_eq!(excerpt.as_bytes(), b"short");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Excerpt {
+    offset: usize,
+    bytes: Vec<u8>,
+}
+
+impl Excerpt {
+    /// copies the bytes of `input` within
+    /// [EXCERPT_RADIUS](crate::EXCERPT_RADIUS) of `index`
+    pub fn of(input: &[u8], index: usize) -> Excerpt {
+        let start = index
+            .saturating_sub(EXCERPT_RADIUS)
+            .min(input.len());
+        let end = index
+            .saturating_add(EXCERPT_RADIUS)
+            .min(input.len());
+        Excerpt::new(start, &input[start..end])
+    }
+
+    /// copies at most `2 * EXCERPT_RADIUS` of the given `bytes`
+    /// found at `offset` of some input
+    pub fn new(offset: usize, bytes: &[u8]) -> Excerpt {
+        let bytes = bytes[..bytes.len().min(2 * EXCERPT_RADIUS)].to_vec();
+        Excerpt { offset, bytes }
+    }
+
+    /// offset within the input of the first byte of this excerpt
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// offset within the input right after the last byte of this excerpt
+    pub fn end(&self) -> usize {
+        self.offset + self.bytes.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// drops the bytes of this excerpt, which are zeroized beforehand
+    /// with the `zeroize` feature, keeping only its offset
+    pub(crate) fn redact(&mut self) {
+        #[cfg(feature = "zeroize")]
+        crate::mem::zeroize(&mut self.bytes);
+        self.bytes.clear();
+    }
+}
+
+impl Debug for Excerpt {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "Excerpt{{offset: {}, bytes: {:02x?}}}", self.offset, self.bytes)
+    }
+}
@@ -0,0 +1,279 @@
+//! table-driven implementation of the Unicode extended grapheme
+//! cluster boundary rules described in [UAX #29](https://www.unicode.org/reports/tr29/)
+use crate::tables::{
+    GRAPHEME_CLUSTER_BREAK, INDIC_CONJUNCT_BREAK_EXTEND, INDIC_CONJUNCT_BREAK_LINKER,
+};
+
+/// `Grapheme_Cluster_Break` property value of a code point.
+///
+/// `ExtendedPictographic` and `InCBConsonant` are not values of
+/// `Grapheme_Cluster_Break` per se but refine `Other` with the
+/// `Extended_Pictographic` and `Indic_Conjunct_Break=Consonant`
+/// properties required by rules GB11 and GB9c respectively.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::GraphemeClusterBreak;
+/// assert_eq!(GraphemeClusterBreak::of('a'), GraphemeClusterBreak::Other);
+/// assert_eq!(GraphemeClusterBreak::of('\u{301}'), GraphemeClusterBreak::Extend);
+/// assert_eq!(GraphemeClusterBreak::of('\u{200d}'), GraphemeClusterBreak::ZWJ);
+/// assert_eq!(GraphemeClusterBreak::of('🔥'), GraphemeClusterBreak::ExtendedPictographic);
+/// assert_eq!(GraphemeClusterBreak::of('🇧'), GraphemeClusterBreak::RegionalIndicator);
+/// ```
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GraphemeClusterBreak {
+    Other,
+    CR,
+    LF,
+    Control,
+    Extend,
+    ZWJ,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    LV,
+    LVT,
+    ExtendedPictographic,
+    InCBConsonant,
+}
+
+impl GraphemeClusterBreak {
+    pub fn of(c: char) -> GraphemeClusterBreak {
+        let c = c as u32;
+        match GRAPHEME_CLUSTER_BREAK.binary_search_by(|&(first, last, _)| {
+            if c < first {
+                core::cmp::Ordering::Greater
+            } else if c > last {
+                core::cmp::Ordering::Less
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        }) {
+            Ok(index) => GRAPHEME_CLUSTER_BREAK[index].2,
+            Err(_) => GraphemeClusterBreak::Other,
+        }
+    }
+}
+
+/// `Indic_Conjunct_Break` property value of a code point
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum IndicConjunctBreak {
+    None,
+    Consonant,
+    Extend,
+    Linker,
+}
+
+impl IndicConjunctBreak {
+    pub(crate) fn of(c: char, gcb: GraphemeClusterBreak) -> IndicConjunctBreak {
+        let c = c as u32;
+        if gcb == GraphemeClusterBreak::InCBConsonant {
+            IndicConjunctBreak::Consonant
+        } else if INDIC_CONJUNCT_BREAK_LINKER
+            .binary_search(&c)
+            .is_ok()
+        {
+            IndicConjunctBreak::Linker
+        } else if INDIC_CONJUNCT_BREAK_EXTEND
+            .binary_search_by(|&(first, last)| {
+                if c < first {
+                    core::cmp::Ordering::Greater
+                } else if c > last {
+                    core::cmp::Ordering::Less
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+        {
+            IndicConjunctBreak::Extend
+        } else {
+            IndicConjunctBreak::None
+        }
+    }
+}
+
+/// progress of the `\p{ExtPict} Extend* ZWJ` prefix required by GB11
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Pictographic {
+    #[default]
+    None,
+    Base,
+    Joined,
+}
+
+/// progress of the `\p{InCB=Consonant} [\p{InCB=Extend}\p{InCB=Linker}]*`
+/// prefix required by GB9c
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Conjunct {
+    #[default]
+    None,
+    Consonant,
+    Linked,
+}
+
+/// Carries the context needed to decide whether a grapheme cluster
+/// boundary exists between the code points fed so far and the next
+/// one.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::GraphemeClusterState;
+/// let mut state = GraphemeClusterState::default();
+/// assert_eq!(state.is_boundary_before('e'), true);
+/// assert_eq!(state.is_boundary_before('\u{301}'), false);
+/// assert_eq!(state.is_boundary_before('x'), true);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GraphemeClusterState {
+    previous: Option<GraphemeClusterBreak>,
+    pictographic: Pictographic,
+    conjunct: Conjunct,
+    regional_indicators: usize,
+}
+
+impl GraphemeClusterState {
+    /// returns `true` if a grapheme cluster boundary exists between
+    /// the previously fed code point and `c`, then records `c` as the
+    /// previous code point.
+    pub fn is_boundary_before(&mut self, c: char) -> bool {
+        let next = GraphemeClusterBreak::of(c);
+        let incb = IndicConjunctBreak::of(c, next);
+        let boundary = match self.previous {
+            None => true, // GB1
+            Some(previous) => self.is_boundary_between(previous, next),
+        };
+        self.advance(next, incb);
+        boundary
+    }
+
+    fn is_boundary_between(
+        &self,
+        previous: GraphemeClusterBreak,
+        next: GraphemeClusterBreak,
+    ) -> bool {
+        use GraphemeClusterBreak::*;
+        match (previous, next) {
+            // GB3
+            (CR, LF) => false,
+            // GB4, GB5
+            (Control | CR | LF, _) | (_, Control | CR | LF) => true,
+            // GB6, GB7, GB8
+            (L, L | V | LV | LVT) | (LV | V, V | T) | (LVT | T, T) => false,
+            // GB9, GB9a, GB9b
+            (_, Extend | ZWJ | SpacingMark) | (Prepend, _) => false,
+            // GB9c
+            (_, InCBConsonant) if self.conjunct == Conjunct::Linked => false,
+            // GB11
+            (ZWJ, ExtendedPictographic)
+                if self.pictographic == Pictographic::Joined =>
+                false,
+            // GB12, GB13
+            (RegionalIndicator, RegionalIndicator) =>
+                self.regional_indicators.is_multiple_of(2),
+            // GB999
+            _ => true,
+        }
+    }
+
+    fn advance(&mut self, next: GraphemeClusterBreak, incb: IndicConjunctBreak) {
+        self.pictographic = match (next, self.pictographic) {
+            (GraphemeClusterBreak::ExtendedPictographic, _) => Pictographic::Base,
+            (GraphemeClusterBreak::Extend, Pictographic::Base) => Pictographic::Base,
+            (GraphemeClusterBreak::ZWJ, Pictographic::Base) => Pictographic::Joined,
+            _ => Pictographic::None,
+        };
+        self.conjunct = match (incb, self.conjunct) {
+            (IndicConjunctBreak::Consonant, _) => Conjunct::Consonant,
+            (IndicConjunctBreak::Linker, Conjunct::Consonant | Conjunct::Linked) =>
+                Conjunct::Linked,
+            (IndicConjunctBreak::Extend, conjunct) => conjunct,
+            _ => Conjunct::None,
+        };
+        self.regional_indicators = if next == GraphemeClusterBreak::RegionalIndicator {
+            self.regional_indicators + 1
+        } else {
+            0
+        };
+        self.previous = Some(next);
+    }
+}
+
+#[cfg(test)]
+mod test_grapheme {
+    use crate::GraphemeClusterState;
+
+    fn clusters(input: &str) -> Vec<String> {
+        let mut state = GraphemeClusterState::default();
+        let mut clusters = Vec::<String>::new();
+        for c in input.chars() {
+            if state.is_boundary_before(c) {
+                clusters.push(String::new());
+            }
+            clusters.last_mut().unwrap().push(c);
+        }
+        clusters
+    }
+
+    #[test]
+    fn test_crlf_and_controls() {
+        assert_eq!(clusters("a\r\nb"), vec!["a", "\r\n", "b"]);
+        assert_eq!(clusters("\n\r"), vec!["\n", "\r"]);
+        assert_eq!(clusters("\u{1}\u{301}"), vec!["\u{1}", "\u{301}"]);
+    }
+
+    #[test]
+    fn test_combining_marks() {
+        assert_eq!(clusters("e\u{301}\u{328}x"), vec!["e\u{301}\u{328}", "x"]);
+        assert_eq!(clusters("\u{915}\u{93f}"), vec!["\u{915}\u{93f}"]);
+        assert_eq!(clusters("\u{600}1"), vec!["\u{600}1"]);
+    }
+
+    #[test]
+    fn test_hangul_syllables() {
+        assert_eq!(
+            clusters("\u{1100}\u{1161}\u{11a8}"),
+            vec!["\u{1100}\u{1161}\u{11a8}"]
+        );
+        assert_eq!(
+            clusters("\u{ac00}\u{11a8}\u{1100}"),
+            vec!["\u{ac00}\u{11a8}", "\u{1100}"]
+        );
+        assert_eq!(clusters("\u{ac01}\u{1161}"), vec!["\u{ac01}", "\u{1161}"]);
+    }
+
+    #[test]
+    fn test_indic_conjuncts() {
+        // क्ष => KA, VIRAMA, SSA
+        assert_eq!(clusters("\u{915}\u{94d}\u{937}"), vec!["\u{915}\u{94d}\u{937}"]);
+        // KA, NUKTA, VIRAMA, ZWJ, SSA
+        assert_eq!(
+            clusters("\u{915}\u{93c}\u{94d}\u{200d}\u{937}"),
+            vec!["\u{915}\u{93c}\u{94d}\u{200d}\u{937}"]
+        );
+        // KA, VIRAMA, A
+        assert_eq!(
+            clusters("\u{915}\u{94d}\u{905}"),
+            vec!["\u{915}\u{94d}", "\u{905}"]
+        );
+    }
+
+    #[test]
+    fn test_emoji_zwj_sequences() {
+        assert_eq!(clusters("👨‍👩‍👧‍👦x"), vec!["👨‍👩‍👧‍👦", "x"]);
+        assert_eq!(clusters("❤️‍🔥"), vec!["❤️‍🔥"]);
+        assert_eq!(clusters("a\u{200d}🔥"), vec!["a\u{200d}", "🔥"]);
+    }
+
+    #[test]
+    fn test_regional_indicators() {
+        assert_eq!(clusters("🇧🇷🇵🇹🇺"), vec!["🇧🇷", "🇵🇹", "🇺"]);
+        assert_eq!(clusters("a🇧🇷"), vec!["a", "🇧🇷"]);
+    }
+}
@@ -1,19 +1,35 @@
 //! heuristic functions to find UTF-8 "[runes](crate::Rune)" within raw [u8] pointers
-use crate::pointer::{
-    self, get_byte_at_index, get_byte_slice_of, is_valid_utf8_str_of,
-};
-use crate::{ByteType, Error, Result};
+use crate::pointer::{get_byte_at_index, get_byte_slice_of};
+use crate::validator::utf8_sequence_len;
+use crate::{is_valid_utf8, ByteType, Error, Excerpt, GraphemeClusterState, Result};
+
+/// default maximum number of code points that may follow the first
+/// code point of a "[rune](crate::Rune)".
+///
+/// The value is borrowed from the limit of 30 non-starters of the
+/// Stream-Safe Text Format of
+/// [UAX #15](https://www.unicode.org/reports/tr15/#Stream_Safe_Text_Format)
+/// but every code point after the first one counts towards it, not
+/// only those whose canonical combining class is not 0.
+pub const DEFAULT_MAX_TRAILING_CODE_POINTS: usize = 30;
 
 /// heuristic function that determines the cutoff index at which a
 /// "[rune](crate::Rune)" ends after the given index.
 ///
-/// # Example
+/// A rune is an extended grapheme cluster as defined by the Unicode
+/// text segmentation rules of [UAX #29](https://www.unicode.org/reports/tr29/),
+/// such that combining marks, Hangul syllables, Indic conjuncts,
+/// emoji modifiers, emoji ZWJ sequences, keycaps and flags are kept
+/// whole. The byte at `index` is treated as the start of the text.
+///
+/// Runes are not limited in length other than by
+/// [DEFAULT_MAX_TRAILING_CODE_POINTS](crate::DEFAULT_MAX_TRAILING_CODE_POINTS),
+/// see [get_rune_cutoff_at_index_with_limit] to configure that limit.
 ///
-/// > Note: `utf8_rune::mem` requires the `mem` feature.
+/// # Example
 ///
 /// ```
-/// use utf8_rune::pointer::{self, get_byte_slice_of};
-/// use utf8_rune::{get_rune_cutoff_at_index, Result};
+/// use utf8_rune::get_rune_cutoff_at_index;
 ///
 /// let bytes = "👩🏻‍🚒👌🏿🧑🏽‍🚒👨‍🚒🌶️🎹💔🔥❤️‍🔥❤️‍🩹".as_bytes();
 /// let length = bytes.len();
@@ -21,103 +37,116 @@ use crate::{ByteType, Error, Result};
 ///
 /// let index = 56;
 /// let cutoff = get_rune_cutoff_at_index(ptr, length, index).unwrap();
-/// let count = cutoff - index;
-/// let slice = get_byte_slice_of(ptr, index, count);
-/// assert_eq!(slice, "🎹".as_bytes());
+/// assert_eq!(&bytes[index..cutoff], "🎹".as_bytes());
 /// ```
 #[inline]
-pub fn get_rune_cutoff_at_index<'g>(
+pub fn get_rune_cutoff_at_index(
     ptr: *const u8,
     length: usize,
     index: usize,
 ) -> Result<usize> {
-    let ptr = pointer::copy(ptr, length)?;
+    get_rune_cutoff_at_index_with_limit(
+        ptr,
+        length,
+        index,
+        DEFAULT_MAX_TRAILING_CODE_POINTS,
+    )
+}
 
+/// equivalent to [`get_rune_cutoff_at_index`] except that the
+/// "[rune](crate::Rune)" may contain at most `max_trailing` code
+/// points after its first code point.
+///
+/// Runes exceeding that limit are reported as
+/// [Error::ClusterTooLong](crate::Error::ClusterTooLong) rather than
+/// being split.
+///
+/// # Example
+///
+/// ```
+/// use utf8_rune::{get_rune_cutoff_at_index_with_limit, Error};
+///
+/// let bytes = "👨‍👩‍👧‍👦".as_bytes();
+/// let length = bytes.len();
+/// let ptr = bytes.as_ptr();
+///
+/// assert_eq!(get_rune_cutoff_at_index_with_limit(ptr, length, 0, 6), Ok(25));
+/// let error = get_rune_cutoff_at_index_with_limit(ptr, length, 0, 5).unwrap_err();
+/// assert!(matches!(error, Error::ClusterTooLong(0, 5, _)));
+/// ```
+#[inline]
+pub fn get_rune_cutoff_at_index_with_limit(
+    ptr: *const u8,
+    length: usize,
+    index: usize,
+    max_trailing: usize,
+) -> Result<usize> {
     if index > length {
-        return Err(Error::InvalidIndex(index, get_byte_slice_of(ptr, 0, length)));
+        return Err(Error::InvalidIndex(index, excerpt_at(ptr, length, index)));
     }
-    if length == 0 {
-        return Ok(index + length);
+    if index == length {
+        return Ok(index);
     }
-    let index = index;
-    let cutoff = index + 1;
+    // two ASCII bytes are separated by a boundary unless they are CR LF
     let byte = get_byte_at_index(ptr, index);
-    let ty = ByteType::from(byte);
-    let max = if ty.is_ascii() {
-        return Ok(cutoff);
-    } else if ty.is_continuation() {
-        return Err(unexpected_continuation_byte_at_index_error(ptr, length, index));
-    } else {
-        ty.len()
+    if byte.is_ascii()
+        && byte != b'\r'
+        && (index + 1 == length || get_byte_at_index(ptr, index + 1).is_ascii())
+    {
+        return Ok(index + 1);
+    }
+    let mut state = GraphemeClusterState::default();
+    let mut cutoff = match char_at_index(ptr, length, index) {
+        Some((c, count)) => {
+            state.is_boundary_before(c);
+            index + count
+        },
+        None => return Err(utf8_error_at_index(ptr, length, index)),
     };
-    let mut cutoff = index + max;
-    let mut max = index + max;
-    let charmax = index + 6; // +6 octets
-    if cutoff <= max && max < length {
-        while max < charmax && cutoff <= max && max < length {
-            let next_byte = get_byte_at_index(ptr, max);
-            let next_ty = ByteType::from(next_byte);
-            let tcutoff = cutoff + next_ty.len();
-            if let Some((count, cty)) = continuation_bytes_location(ptr, length, cutoff)
-            {
-                let tcutoff = cutoff + count;
-                if is_valid_utf8_str_of(ptr, index, tcutoff - index) {
-                    cutoff = tcutoff;
-                    break;
-                } else {
-                    max += cty.len();
-                }
-            } else if next_ty.has_rune_delta() {
-                if next_ty.len() < 4 {
-                    if let Some((_count_, _cty_)) =
-                        continuation_bytes_location(ptr, length, cutoff - index)
-                    {
-                        cutoff += next_ty.len();
-                    }
-                    break;
-                } else if let Some((count, cty)) =
-                    continuation_bytes_location(ptr, length, tcutoff)
-                {
-                    let tcutoff = cutoff + count + cty.len();
-                    cutoff = tcutoff;
-                    break;
-                } else {
-                    let delta = tcutoff - cutoff;
-                    match delta {
-                        4 => {
-                            let mut next_chunk = [0u8; 4];
-                            let next_bytes = get_byte_slice_of(
-                                ptr,
-                                index + delta,
-                                cutoff - index + delta,
-                            );
-                            next_chunk.copy_from_slice(&next_bytes[0..4]);
-                            match next_chunk {
-                                [0xF0, 0x9F, 0x8F, 0xBB]
-                                | [0xF0, 0x9F, 0x8F, 0xBC]
-                                | [0xF0, 0x9F, 0x8F, 0xBD]
-                                | [0xF0, 0x9F, 0x8F, 0xBE]
-                                | [0xF0, 0x9F, 0x8F, 0xBF] => {
-                                    cutoff += delta;
-                                    break;
-                                },
-                                _ => {},
-                            }
-                        },
-                        _ => {},
-                    }
-
-                    break;
+    let mut trailing = 0;
+    while cutoff < length {
+        match char_at_index(ptr, length, cutoff) {
+            Some((c, count)) if !state.is_boundary_before(c) => {
+                trailing += 1;
+                if trailing > max_trailing {
+                    return Err(Error::ClusterTooLong(
+                        index,
+                        max_trailing,
+                        excerpt_at(ptr, length, index),
+                    ));
                 }
-            } else if next_ty.is_ascii() {
-                break;
-            } else {
-            }
-            cutoff += 1;
+                cutoff += count;
+            },
+            _ => break,
         }
     }
-    return Ok(cutoff);
+    Ok(cutoff)
 }
+
+/// decodes the `char` whose UTF-8 sequence starts at `index` along
+/// with the length of that sequence, or `None` if the bytes at
+/// `index` are not a valid UTF-8 sequence.
+#[inline]
+pub(crate) fn char_at_index(
+    ptr: *const u8,
+    length: usize,
+    index: usize,
+) -> Option<(char, usize)> {
+    if index >= length {
+        return None;
+    }
+    let count = utf8_sequence_len(get_byte_slice_of(ptr, index, length - index))?;
+    let bytes = get_byte_slice_of(ptr, index, count);
+    let lead = match count {
+        1 => bytes[0],
+        _ => bytes[0] & (0x7F >> count),
+    };
+    let code = bytes[1..]
+        .iter()
+        .fold(lead as u32, |code, byte| (code << 6) | (byte & 0x3F) as u32);
+    char::from_u32(code).map(|c| (c, count))
+}
+
 /// equivalent to calling [`get_rune_cutoff_at_index`] with index 0
 ///
 /// # Example
@@ -131,7 +160,7 @@ pub fn get_rune_cutoff_at_index<'g>(
 /// assert_eq!(std::str::from_utf8(&bytes[0..6]), Ok("☠️"));
 /// ```
 #[inline]
-pub fn split_at_first_rune<'g>(ptr: *const u8, length: usize) -> usize {
+pub fn split_at_first_rune(ptr: *const u8, length: usize) -> usize {
     get_rune_cutoff_at_index(ptr, length, 0).expect("should not fail at index 0")
 }
 
@@ -171,7 +200,7 @@ pub fn split_at_first_rune<'g>(ptr: *const u8, length: usize) -> usize {
 /// ```
 ///
 #[inline]
-pub fn continuation_bytes_location<'g>(
+pub fn continuation_bytes_location(
     ptr: *const u8,
     length: usize,
     index: usize,
@@ -181,9 +210,8 @@ pub fn continuation_bytes_location<'g>(
         let zwj0 = get_byte_at_index(ptr, index);
         let zwj1 = get_byte_at_index(ptr, index + 1);
         let zwj2 = get_byte_at_index(ptr, index + 2);
-        let next_rune_byte = get_byte_at_index(ptr, index + shift);
         let ty = if index + shift < length {
-            ByteType::from(next_rune_byte)
+            ByteType::from(get_byte_at_index(ptr, index + shift))
         } else {
             ByteType::None
         };
@@ -210,73 +238,58 @@ pub fn continuation_bytes_location<'g>(
     }
 }
 
+/// walks back from `index` to the closest lead byte whose UTF-8
+/// sequence is valid, feeding only the bytes of that sequence to
+/// [Utf8State](crate::Utf8State) such that the cost is linear in the
+/// distance walked.
 #[inline]
-pub(crate) fn previous_valid_cutoff<'e>(
+pub(crate) fn previous_valid_cutoff(
     ptr: *const u8,
     length: usize,
     index: usize,
 ) -> Option<usize> {
-    let ptr = pointer::copy(ptr, length).unwrap();
-    if index == 0 && index == length {
+    if length == 0 {
         return None;
     }
-    let mut previous_index = index;
-    let mut byte = get_byte_at_index(ptr, previous_index);
-    #[allow(unused_assignments)]
-    let mut ty = ByteType::from(byte);
-
-    while previous_index > 0 {
-        ty = ByteType::from(byte);
-        if !ty.has_rune_delta() {
+    let mut previous_index = index.min(length - 1);
+    loop {
+        while previous_index > 0
+            && !ByteType::from(get_byte_at_index(ptr, previous_index)).has_rune_delta()
+        {
             previous_index -= 1;
-            byte = get_byte_at_index(ptr, previous_index);
-        } else {
-            break;
         }
-    }
-    if previous_index == length {
-        None
-    } else {
-        byte = get_byte_at_index(ptr, previous_index);
+        let ty = ByteType::from(get_byte_at_index(ptr, previous_index));
         if let Some((count, cty)) =
             continuation_bytes_location(ptr, length, previous_index)
         {
             if previous_index >= cty.len() {
-                if is_valid_utf8_str_of(ptr, previous_index - cty.len(), count) {
-                    previous_index -= cty.len();
-                    Some(previous_index)
-                } else {
-                    previous_index -= 1;
-                    previous_valid_cutoff(ptr, length, previous_index)
+                let start = previous_index - cty.len();
+                let count = count.min(length - start);
+                if is_valid_utf8(get_byte_slice_of(ptr, start, count)) {
+                    return Some(start);
                 }
-            } else {
-                if previous_index > 0 && previous_index <= cty.len() {
-                    previous_index -= 1;
-                    return previous_valid_cutoff(ptr, length, previous_index);
-                }
-
-                Some(previous_index)
+            } else if previous_index == 0 || previous_index > cty.len() {
+                return Some(previous_index);
             }
-        } else if ByteType::from(byte).has_rune_delta() {
-            let cty = ByteType::from(byte);
-            if is_valid_utf8_str_of(ptr, previous_index, length - previous_index) {
+        } else if ty.has_rune_delta() {
+            let count = length - previous_index;
+            if utf8_sequence_len(get_byte_slice_of(ptr, previous_index, count))
+                .is_some()
+                || previous_index < ty.len()
+            {
                 return Some(previous_index);
-            } else if previous_index >= cty.len() {
-                previous_index -= 1;
-                return previous_valid_cutoff(ptr, length, previous_index);
-            } else {
-                Some(previous_index)
             }
         } else if previous_index == 0 {
-            None
+            return None;
         } else {
-            Some(previous_index)
+            return Some(previous_index);
         }
+        previous_index = previous_index.checked_sub(1)?;
     }
 }
 
 #[inline]
-pub(crate) fn next_valid_cutoff<'e>(
+pub(crate) fn next_valid_cutoff(
     ptr: *const u8,
     length: usize,
     index: usize,
@@ -287,7 +300,6 @@ pub(crate) fn next_valid_cutoff<'e>(
     if index >= length {
         return None;
     }
-    let ptr = pointer::copy(ptr, length).unwrap();
     let mut next_index = index;
 
     while next_index < length {
@@ -320,16 +332,77 @@ pub(crate) fn next_valid_cutoff<'e>(
     }
 }
 
-pub(crate) fn unexpected_continuation_byte_at_index_error<'e>(
+pub(crate) fn unexpected_continuation_byte_at_index_error(
     ptr: *const u8,
     length: usize,
     index: usize,
-) -> Error<'e> {
+) -> Error {
     let byte = get_byte_at_index(ptr, index);
     let previous_index = previous_valid_cutoff(ptr, length, index);
     let next_index = next_valid_cutoff(ptr, length, index);
-    let slice = get_byte_slice_of(ptr, index, length);
-    Error::UnexpectedContinuationByte(byte, index, previous_index, next_index, slice)
+    let excerpt = excerpt_at(ptr, length, index);
+    Error::UnexpectedContinuationByte(byte, index, previous_index, next_index, excerpt)
+}
+
+/// describes the invalid UTF-8 sequence at `index`, where a
+/// continuation byte is reported along with the valid cutoffs
+/// surrounding it
+pub(crate) fn utf8_error_at_index(
+    ptr: *const u8,
+    length: usize,
+    index: usize,
+) -> Error {
+    if ByteType::from(get_byte_at_index(ptr, index)).is_continuation() {
+        return unexpected_continuation_byte_at_index_error(ptr, length, index);
+    }
+    let window = get_byte_slice_of(ptr, index, (length - index).min(4));
+    invalid_sequence_error(window, index, excerpt_at(ptr, length, index))
+}
+
+/// classifies the invalid UTF-8 sequence at the start of `window`
+/// which holds the bytes of the input from `index` onwards, see
+/// [Error](crate::Error) for the possible variants.
+///
+/// The valid cutoffs surrounding a continuation byte are left out as
+/// `window` does not hold the input before `index`, see
+/// [utf8_error_at_index] to obtain them.
+pub(crate) fn invalid_sequence_error(
+    window: &[u8],
+    index: usize,
+    excerpt: Excerpt,
+) -> Error {
+    let window = &window[..window.len().min(4)];
+    let actual = core::str::from_utf8(window)
+        .err()
+        .and_then(|error| error.error_len())
+        .unwrap_or(window.len());
+    let lead = window[0];
+    let expected = match lead {
+        0xC0..=0xF7 => lead.leading_ones() as usize,
+        _ => 0,
+    };
+    match (lead, window.get(1)) {
+        (0x80..=0xBF, _) =>
+            Error::UnexpectedContinuationByte(lead, index, None, None, excerpt),
+        (0xF8..=0xFF, _) => Error::InvalidLeadByte(index, expected, actual, excerpt),
+        (0xC0 | 0xC1, _) | (0xE0, Some(0x80..=0x9F)) | (0xF0, Some(0x80..=0x8F)) =>
+            Error::OverlongEncoding(index, expected, actual, excerpt),
+        (0xED, Some(0xA0..=0xBF)) =>
+            Error::EncodedSurrogate(index, expected, actual, excerpt),
+        (0xF4, Some(0x90..=0xBF)) | (0xF5..=0xF7, _) =>
+            Error::CodePointTooLarge(index, expected, actual, excerpt),
+        _ => Error::TruncatedSequence(index, expected, actual, excerpt),
+    }
+}
+
+/// copies the bytes of the input at `ptr` surrounding `index` such
+/// that the [Error](crate::Error) remains valid after the caller
+/// deallocates its input.
+fn excerpt_at(ptr: *const u8, length: usize, index: usize) -> Excerpt {
+    if ptr.is_null() || length == 0 {
+        return Excerpt::new(0, &[]);
+    }
+    Excerpt::of(get_byte_slice_of(ptr, 0, length), index)
 }
 
 #[cfg(test)]
@@ -401,7 +474,11 @@ mod test_split_at_first_rune {
 #[cfg(test)]
 mod test_get_rune_cutoff_at_index {
     use crate::pointer::{self};
-    use crate::{assert_get_rune_cutoff_at_index, get_rune_cutoff_at_index, Result};
+    use crate::{
+        assert_get_rune_cutoff_at_index, get_rune_cutoff_at_index,
+        get_rune_cutoff_at_index_with_limit, Error, ErrorKind, Result,
+        DEFAULT_MAX_TRAILING_CODE_POINTS, EXCERPT_RADIUS,
+    };
     #[test]
     fn test_get_rune_cutoff_at_first_index_single_rune() -> Result<()> {
         let (ptr, length) = pointer::from_slice("❤️".as_bytes())?;
@@ -504,6 +581,106 @@ mod test_get_rune_cutoff_at_index {
 
         Ok(())
     }
+    #[test]
+    fn test_get_rune_cutoff_combining_marks() -> Result<()> {
+        // "é" => [0x65, 0xcc, 0x81] => "e" followed by U+0301 COMBINING ACUTE ACCENT
+        let (ptr, length) = pointer::from_slice("cafe\u{301}s".as_bytes())?;
+        assert_get_rune_cutoff_at_index!(ptr, length, 7, 3, 6, "e\u{301}");
+        assert_get_rune_cutoff_at_index!(ptr, length, 7, 6, 7, "s");
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_rune_cutoff_hangul_jamo() -> Result<()> {
+        // "각" => U+1100 CHOSEONG KIYEOK, U+1161 JUNGSEONG A, U+11A8 JONGSEONG KIYEOK
+        let (ptr, length) =
+            pointer::from_slice("\u{1100}\u{1161}\u{11a8}!".as_bytes())?;
+        assert_get_rune_cutoff_at_index!(
+            ptr,
+            length,
+            10,
+            0,
+            9,
+            "\u{1100}\u{1161}\u{11a8}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_rune_cutoff_regional_indicator_flags() -> Result<()> {
+        let (ptr, length) = pointer::from_slice("🇧🇷🇵🇹".as_bytes())?;
+        assert_get_rune_cutoff_at_index!(ptr, length, 16, 0, 8, "🇧🇷");
+        assert_get_rune_cutoff_at_index!(ptr, length, 16, 8, 16, "🇵🇹");
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_rune_cutoff_keycap() -> Result<()> {
+        // "1️⃣" => "1" followed by U+FE0F and U+20E3 COMBINING ENCLOSING KEYCAP
+        let (ptr, length) = pointer::from_slice("1️⃣2".as_bytes())?;
+        assert_get_rune_cutoff_at_index!(ptr, length, 8, 0, 7, "1️⃣");
+        assert_get_rune_cutoff_at_index!(ptr, length, 8, 7, 8, "2");
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_rune_cutoff_indic_conjunct() -> Result<()> {
+        // "क्षि" => KA, VIRAMA, SSA, VOWEL SIGN I
+        let (ptr, length) =
+            pointer::from_slice("\u{915}\u{94d}\u{937}\u{93f}".as_bytes())?;
+        assert_get_rune_cutoff_at_index!(
+            ptr,
+            length,
+            12,
+            0,
+            12,
+            "\u{915}\u{94d}\u{937}\u{93f}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_rune_cutoff_long_zwj_sequences() -> Result<()> {
+        // "👨‍👩‍👧‍👦" => 4 emoji joined by 3 U+200D => 25 bytes
+        // "🧑🏻‍❤️‍💋‍🧑🏼" => 10 code points => 35 bytes
+        let (ptr, length) = pointer::from_slice("👨‍👩‍👧‍👦🧑🏻‍❤️‍💋‍🧑🏼".as_bytes())?;
+        assert_get_rune_cutoff_at_index!(ptr, length, 60, 0, 25, "👨‍👩‍👧‍👦");
+        assert_get_rune_cutoff_at_index!(ptr, length, 60, 25, 60, "🧑🏻‍❤️‍💋‍🧑🏼");
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_rune_cutoff_cluster_too_long() -> Result<()> {
+        let input = format!("e{}x", "\u{301}".repeat(DEFAULT_MAX_TRAILING_CODE_POINTS));
+        let (ptr, length) = pointer::from_display(&input)?;
+        assert_eq!(get_rune_cutoff_at_index(ptr, length, 0), Ok(length - 1));
+
+        let input =
+            format!("e{}x", "\u{301}".repeat(DEFAULT_MAX_TRAILING_CODE_POINTS + 1));
+        let (ptr, length) = pointer::from_display(&input)?;
+        let cutoff = get_rune_cutoff_at_index(ptr, length, 0);
+        assert!(matches!(
+            cutoff,
+            Err(Error::ClusterTooLong(0, DEFAULT_MAX_TRAILING_CODE_POINTS, _))
+        ));
+
+        let (ptr, length) = pointer::from_display("a1️⃣")?;
+        assert_eq!(get_rune_cutoff_at_index_with_limit(ptr, length, 1, 2), Ok(8));
+        let cutoff = get_rune_cutoff_at_index_with_limit(ptr, length, 1, 1);
+        assert!(matches!(cutoff, Err(Error::ClusterTooLong(1, 1, _))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_rune_cutoff_invalid_lead_byte() -> Result<()> {
+        let (ptr, length) = pointer::from_slice(&[b'a', 0xFF, b'b'])?;
+        assert_eq!(get_rune_cutoff_at_index(ptr, length, 0), Ok(1));
+        let cutoff = get_rune_cutoff_at_index(ptr, length, 1);
+        assert!(matches!(cutoff, Err(Error::InvalidLeadByte(1, 0, 1, _))));
+        assert_eq!(get_rune_cutoff_at_index(ptr, length, 2), Ok(3));
+        Ok(())
+    }
+
     #[macro_export]
     macro_rules! assert_get_rune_cutoff_at_index {
     (
@@ -516,7 +693,7 @@ mod test_get_rune_cutoff_at_index {
         $(,)?
     ) => {{
         use debug_et_diagnostics::{ansi, fore, from_bytes, indent};
-        use crate::{get_byte_slice_of, format_bytes, RuneParts};
+        use $crate::{get_byte_slice_of, format_bytes, RuneParts};
 
         // debug_et_diagnostics::step!(fg=line, format!("expecting {} from index..cutoff {}..{}", $expected, $index, $cutoff));
 
@@ -589,11 +766,11 @@ mod test_get_rune_cutoff_at_index {
 
         let index = $index;
         let length = $length - index;
-        let actual = match RuneParts::from_raw_parts($ptr, $length)
-            .rune_at_index($index) {
+        let actual = match RuneParts::from_bytes(get_byte_slice_of($ptr, 0, $length))
+            .and_then(|parts| parts.rune_at_index($index)) {
                 Ok(actual) => actual.as_str().to_string(),
                 Err(error) => {
-                    panic!("{}:{} RuneParts::from_raw_parts({:#?}, {})\n{error}", file!(), line!(), $ptr, $length);
+                    panic!("{}:{} RuneParts::from_bytes({:#?}, {})\n{error}", file!(), line!(), $ptr, $length);
                 }
             };
 
@@ -641,6 +818,50 @@ mod test_get_rune_cutoff_at_index {
     }};
 }
 
+    #[test]
+    fn test_get_rune_cutoff_invalid_utf8_taxonomy() {
+        for (bytes, kind, expected, actual) in [
+            (&b"\xE2\x82"[..], ErrorKind::TruncatedSequence, 3, 2),
+            (&b"\xF0\x9F\xA6!"[..], ErrorKind::TruncatedSequence, 4, 3),
+            (&b"\xC0\xAF"[..], ErrorKind::OverlongEncoding, 2, 1),
+            (&b"\xC1\xBF"[..], ErrorKind::OverlongEncoding, 2, 1),
+            (&b"\xE0\x9F\xBF"[..], ErrorKind::OverlongEncoding, 3, 1),
+            (&b"\xF0\x8F\xBF\xBF"[..], ErrorKind::OverlongEncoding, 4, 1),
+            (&b"\xED\xA0\x80"[..], ErrorKind::EncodedSurrogate, 3, 1),
+            (&b"\xF4\x90\x80\x80"[..], ErrorKind::CodePointTooLarge, 4, 1),
+            (&b"\xF5\x80\x80\x80"[..], ErrorKind::CodePointTooLarge, 4, 1),
+            (&b"\xF7\xBF\xBF\xBF"[..], ErrorKind::CodePointTooLarge, 4, 1),
+            (&b"\xF8\x88\x80\x80\x80"[..], ErrorKind::InvalidLeadByte, 0, 1),
+            (&b"\xFF"[..], ErrorKind::InvalidLeadByte, 0, 1),
+        ] {
+            let mut input = b"ok".to_vec();
+            input.extend_from_slice(bytes);
+            let error =
+                get_rune_cutoff_at_index(input.as_ptr(), input.len(), 2).unwrap_err();
+            assert_eq!(error.kind(), kind, "{bytes:02x?}");
+            assert_eq!(error.offset(), Some(2), "{bytes:02x?}");
+            assert_eq!(error.byte_counts(), Some((expected, actual)), "{bytes:02x?}");
+        }
+    }
+
+    #[test]
+    fn test_error_owns_bounded_excerpt() {
+        fn assert_send_sync_static<T: Send + Sync + 'static>(_: &T) {}
+
+        let mut bytes = vec![b'x'; 100];
+        bytes[60] = 0x80;
+        let error =
+            get_rune_cutoff_at_index(bytes.as_ptr(), bytes.len(), 60).unwrap_err();
+        drop(bytes);
+        assert_send_sync_static(&error);
+        assert_eq!(error.kind(), ErrorKind::UnexpectedContinuationByte);
+        assert_eq!(error.offset(), Some(60));
+        let excerpt = error.excerpt().unwrap();
+        assert_eq!(excerpt.offset(), 60 - EXCERPT_RADIUS);
+        assert_eq!(excerpt.len(), 2 * EXCERPT_RADIUS);
+        assert_eq!(excerpt.as_bytes()[EXCERPT_RADIUS], 0x80);
+    }
+
     fn format_expected_rune(c: &str) -> String {
         use debug_et_diagnostics::color::byte_hex;
         format!(
@@ -672,7 +893,7 @@ mod test_continuation_bytes_location {
         );
         let location = continuation_bytes_location(ptr, length, 8);
 
-        assert_eq!(location.is_some(), true);
+        assert!(location.is_some());
         let (count, ty) = location.unwrap();
         assert_eq!(ty.len(), 4);
         assert_eq!(ty.byte(), 0xF0);
@@ -692,7 +913,7 @@ mod test_continuation_bytes_location {
         );
         let location = continuation_bytes_location(ptr, length, 3);
 
-        assert_eq!(location.is_some(), true);
+        assert!(location.is_some());
         let (count, ty) = location.unwrap();
         assert_eq!(ty.len(), 3);
         assert_eq!(ty.byte(), 0xE2);
@@ -837,10 +1058,10 @@ mod test_next_valid_cutoff {
     fn test_next_valid_cutoff_at_various_indexes_94_bytes() -> Result<()> {
         let (ptr, length) = pointer::from_slice("👩🏻‍🚒👌🏿🧑🏽‍🚒👨‍🚒🌶️🎹💔🔥❤️‍🔥❤️‍🩹".as_bytes())?;
         assert_some_next_valid_cutoff!(ptr, length, 94, 0, 0, "👩🏻‍🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 1, 4, "🏻\u{200d}🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 2, 4, "🏻\u{200d}🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 3, 4, "🏻\u{200d}🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 4, 4, "🏻\u{200d}🚒");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 1, 4, "🏻\u{200d}");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 2, 4, "🏻\u{200d}");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 3, 4, "🏻\u{200d}");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 4, 4, "🏻\u{200d}");
         assert_some_next_valid_cutoff!(ptr, length, 94, 5, 15, "👌🏿");
         assert_some_next_valid_cutoff!(ptr, length, 94, 15, 15, "👌🏿");
         assert_some_next_valid_cutoff!(ptr, length, 94, 16, 19, "🏿");
@@ -852,17 +1073,17 @@ mod test_next_valid_cutoff {
         assert_some_next_valid_cutoff!(ptr, length, 94, 22, 23, "🧑🏽‍🚒");
         assert_some_next_valid_cutoff!(ptr, length, 94, 23, 23, "🧑🏽‍🚒");
 
-        assert_some_next_valid_cutoff!(ptr, length, 94, 24, 27, "🏽\u{200d}🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 25, 27, "🏽\u{200d}🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 26, 27, "🏽\u{200d}🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 27, 27, "🏽\u{200d}🚒");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 24, 27, "🏽\u{200d}");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 25, 27, "🏽\u{200d}");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 26, 27, "🏽\u{200d}");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 27, 27, "🏽\u{200d}");
         assert_some_next_valid_cutoff!(ptr, length, 94, 28, 38, "👨\u{200d}🚒");
         assert_some_next_valid_cutoff!(ptr, length, 94, 29, 38, "👨\u{200d}🚒");
         assert_some_next_valid_cutoff!(ptr, length, 94, 30, 38, "👨\u{200d}🚒");
         assert_some_next_valid_cutoff!(ptr, length, 94, 31, 38, "👨\u{200d}🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 32, 34, "🚒👨\u{200d}🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 33, 34, "🚒👨\u{200d}🚒");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 34, 34, "🚒👨\u{200d}🚒");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 32, 34, "🚒");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 33, 34, "🚒");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 34, 34, "🚒");
         assert_some_next_valid_cutoff!(ptr, length, 94, 35, 38, "👨\u{200d}🚒");
         assert_some_next_valid_cutoff!(ptr, length, 94, 36, 38, "👨\u{200d}🚒");
         assert_some_next_valid_cutoff!(ptr, length, 94, 37, 38, "👨\u{200d}🚒");
@@ -871,9 +1092,9 @@ mod test_next_valid_cutoff {
         assert_some_next_valid_cutoff!(ptr, length, 94, 40, 49, "🌶️");
         assert_some_next_valid_cutoff!(ptr, length, 94, 41, 49, "🌶️");
         assert_some_next_valid_cutoff!(ptr, length, 94, 42, 49, "🌶️");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 43, 45, "🚒🌶️");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 44, 45, "🚒🌶️");
-        assert_some_next_valid_cutoff!(ptr, length, 94, 45, 45, "🚒🌶️");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 43, 45, "🚒");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 44, 45, "🚒");
+        assert_some_next_valid_cutoff!(ptr, length, 94, 45, 45, "🚒");
         assert_some_next_valid_cutoff!(ptr, length, 94, 46, 49, "🌶️");
         assert_some_next_valid_cutoff!(ptr, length, 94, 47, 49, "🌶️");
         assert_some_next_valid_cutoff!(ptr, length, 94, 48, 49, "🌶️");
@@ -946,7 +1167,7 @@ mod test_next_valid_cutoff {
         assert!(result.is_some(), "expected next_valid_cutoff at {} to not be None", $invalid_index);
         let actual = result.unwrap();
         assert_eq!(actual, $expected_valid_index, "expected next_valid_cutoff to be {} rather than {}", $expected_valid_index, actual);
-        let parts = RuneParts::from_raw_parts($ptr, $length);
+        let parts = RuneParts::from_bytes($crate::pointer::get_byte_slice_of($ptr, 0, $length)).unwrap();
         let result = parts.rune_at_index(actual);
         assert!(result.is_ok(), "expected valid Rune at index to be {} but got error: {}", $expected_valid_index, result.err().map(|err|err.to_string()).unwrap_or_default());
         let rune = result.unwrap();
@@ -1111,6 +1332,17 @@ mod test_previous_valid_cutoff {
         Ok(())
     }
 
+    #[test]
+    fn test_previous_valid_cutoff_after_stray_continuation_byte() -> Result<()> {
+        let mut input = "é".repeat(1 << 20).into_bytes();
+        input.push(0x80);
+        input.extend_from_slice(b"tail");
+        let (ptr, length) = (input.as_ptr(), input.len());
+        assert_eq!(previous_valid_cutoff(ptr, length, 2 << 20), Some((2 << 20) - 2));
+        assert_eq!(previous_valid_cutoff(ptr, length, length), Some((2 << 20) - 2));
+        Ok(())
+    }
+
     #[test]
     fn test_previous_valid_cutoff_at_various_indexes_94_bytes() -> Result<()> {
         let (ptr, length) = pointer::from_display("👩🏻‍🚒👌🏿🧑🏽‍🚒👨‍🚒🌶️🎹💔🔥❤️‍🔥❤️‍🩹")?;
@@ -1118,13 +1350,13 @@ mod test_previous_valid_cutoff {
         assert_some_previous_valid_cutoff!(ptr, length, 94, 1, 0, "👩🏻‍🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 2, 0, "👩🏻‍🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 3, 0, "👩🏻‍🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 4, 4, "🏻\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 5, 4, "🏻\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 6, 4, "🏻\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 7, 4, "🏻\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 8, 4, "🏻\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 9, 4, "🏻\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 10, 4, "🏻\u{200d}🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 4, 4, "🏻\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 5, 4, "🏻\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 6, 4, "🏻\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 7, 4, "🏻\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 8, 4, "🏻\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 9, 4, "🏻\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 10, 4, "🏻\u{200d}");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 11, 11, "🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 12, 11, "🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 13, 11, "🚒");
@@ -1141,18 +1373,18 @@ mod test_previous_valid_cutoff {
         assert_some_previous_valid_cutoff!(ptr, length, 94, 24, 23, "🧑🏽‍🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 25, 23, "🧑🏽‍🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 26, 23, "🧑🏽‍🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 27, 27, "🏽\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 28, 27, "🏽\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 29, 27, "🏽\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 30, 27, "🏽\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 31, 27, "🏽\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 32, 27, "🏽\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 33, 27, "🏽\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 34, 34, "🚒👨\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 35, 34, "🚒👨\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 36, 34, "🚒👨\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 36, 34, "🚒👨\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 37, 34, "🚒👨\u{200d}🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 27, 27, "🏽\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 28, 27, "🏽\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 29, 27, "🏽\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 30, 27, "🏽\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 31, 27, "🏽\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 32, 27, "🏽\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 33, 27, "🏽\u{200d}");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 34, 34, "🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 35, 34, "🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 36, 34, "🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 36, 34, "🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 37, 34, "🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 38, 38, "👨\u{200d}🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 39, 38, "👨\u{200d}🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 40, 38, "👨\u{200d}🚒");
@@ -1160,12 +1392,12 @@ mod test_previous_valid_cutoff {
         assert_some_previous_valid_cutoff!(ptr, length, 94, 42, 38, "👨\u{200d}🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 43, 38, "👨\u{200d}🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 44, 38, "👨\u{200d}🚒");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 45, 45, "🚒🌶\u{fe0f}");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 46, 45, "🚒🌶️");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 47, 45, "🚒🌶️");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 46, 45, "🚒🌶️");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 47, 45, "🚒🌶️");
-        assert_some_previous_valid_cutoff!(ptr, length, 94, 48, 45, "🚒🌶️");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 45, 45, "🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 46, 45, "🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 47, 45, "🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 46, 45, "🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 47, 45, "🚒");
+        assert_some_previous_valid_cutoff!(ptr, length, 94, 48, 45, "🚒");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 49, 49, "🌶️");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 50, 49, "🌶️");
         assert_some_previous_valid_cutoff!(ptr, length, 94, 51, 49, "🌶️");
@@ -1235,7 +1467,7 @@ mod test_previous_valid_cutoff {
         assert!(result.is_some(), "expected previous_valid_cutoff at {} to not be None", $invalid_index);
         let actual = result.unwrap();
         assert_eq!(actual, $expected_valid_index, "expected previous_valid_cutoff to be {} rather than {}", $expected_valid_index, actual);
-        let parts = RuneParts::from_raw_parts($ptr, $length);
+        let parts = RuneParts::from_bytes($crate::pointer::get_byte_slice_of($ptr, 0, $length)).unwrap();
         let result = parts.rune_at_index(actual);
         assert!(result.is_ok(), "expected valid Rune at index to be {} but got error: {}", $expected_valid_index, result.err().map(|err|err.to_string()).unwrap_or_default());
         let rune = result.unwrap();
@@ -1,4 +1,7 @@
-use std::iter::Iterator;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::iter::Iterator;
 
 pub const DEFAULT_INDENT: usize = 4;
 use crate::Error;
@@ -34,7 +37,7 @@ pub fn format_bytes(bytes: &[u8], indent: Option<usize>) -> String {
     format!(
         "{pad}[{}]",
         bytes
-            .into_iter()
+            .iter()
             .map(|c| c.to_string())
             .collect::<Vec<String>>()
             .join(", ")
@@ -42,15 +45,14 @@ pub fn format_bytes(bytes: &[u8], indent: Option<usize>) -> String {
 }
 
 #[cfg(feature = "debug")]
-pub fn display_error<'e>(error: Error<'e>, ptr: *const u8, length: usize) {
+pub fn display_error(error: Error, ptr: *const u8, length: usize) {
     let filename = file!();
     let lineno = line!();
     eprintln!("{filename}:{lineno} {error}");
 }
 #[cfg(not(feature = "debug"))]
-pub fn display_error<'e>(_error: Error<'e>, _ptr: *const u8, _length: usize) {}
-
+pub fn display_error(_error: Error, _ptr: *const u8, _length: usize) {}
 
 pub fn unwrap_indent(indent: Option<usize>) -> usize {
-    indent.unwrap_or_else(|| DEFAULT_INDENT)
+    indent.unwrap_or(DEFAULT_INDENT)
 }
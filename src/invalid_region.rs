@@ -0,0 +1,57 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::ops::Range;
+
+/// Represents a contiguous span of invalid UTF-8 bytes found while
+/// segmenting, see
+/// [Runes::from_utf8_collecting_errors](crate::Runes::from_utf8_collecting_errors).
+///
+/// `previous_valid_cutoff` and `next_valid_cutoff` are the offsets at
+/// which the valid UTF-8 surrounding the span ends and resumes, or
+/// `None` when the span is at the start or at the end of the input.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::Runes;
+/// let (_, regions) = Runes::from_utf8_collecting_errors(b"ok\x80\xBF!").unwrap();
+/// assert_eq!(regions.len(), 1);
+/// assert_eq!(regions[0].range, 2..4);
+/// assert_eq!(regions[0].bytes, vec![0x80, 0xBF]);
+/// assert_eq!(regions[0].to_string(), "invalid UTF-8 bytes [0x80, 0xbf] at 2..4");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidRegion {
+    pub range: Range<usize>,
+    pub bytes: Vec<u8>,
+    pub previous_valid_cutoff: Option<usize>,
+    pub next_valid_cutoff: Option<usize>,
+}
+
+impl InvalidRegion {
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+}
+
+impl Display for InvalidRegion {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "invalid UTF-8 bytes [{}] at {}..{}",
+            self.bytes
+                .iter()
+                .map(|byte| format!("0x{byte:02x}"))
+                .collect::<Vec<String>>()
+                .join(", "),
+            self.range.start,
+            self.range.end,
+        )
+    }
+}
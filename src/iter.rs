@@ -0,0 +1,300 @@
+//! iterators over the "[runes](crate::Rune)" of [Runes](crate::Runes)
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::slice::Windows;
+
+use crate::{Rune, RuneArena, RuneRef, Runes};
+
+/// Iterator over the [RuneRef(s)](crate::RuneRef) of
+/// [Runes](crate::Runes), see [Runes::iter](crate::Runes::iter).
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::Runes;
+/// let runes = Runes::new("👌🏽❤️‍🔥x");
+/// let mut iter = runes.iter();
+/// assert_eq!(iter.len(), 3);
+/// assert_eq!(iter.next().unwrap(), "👌🏽");
+/// assert_eq!(iter.next_back().unwrap(), "x");
+/// assert_eq!(iter.len(), 1);
+/// assert_eq!(iter.next().unwrap(), "❤️‍🔥");
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Clone)]
+pub struct RunesIter<'r> {
+    bytes: &'r [u8],
+    windows: Windows<'r, usize>,
+}
+
+impl<'r> RunesIter<'r> {
+    pub(crate) fn new(bytes: &'r [u8], indexes: &'r [usize]) -> RunesIter<'r> {
+        RunesIter {
+            bytes,
+            windows: indexes.windows(2),
+        }
+    }
+
+    fn rune_ref(&self, window: &[usize]) -> RuneRef<'r> {
+        RuneRef::new(&self.bytes[window[0]..window[1]])
+    }
+}
+
+impl<'r> Iterator for RunesIter<'r> {
+    type Item = RuneRef<'r>;
+
+    fn next(&mut self) -> Option<RuneRef<'r>> {
+        let window = self.windows.next()?;
+        Some(self.rune_ref(window))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+}
+
+impl<'r> DoubleEndedIterator for RunesIter<'r> {
+    fn next_back(&mut self) -> Option<RuneRef<'r>> {
+        let window = self.windows.next_back()?;
+        Some(self.rune_ref(window))
+    }
+}
+
+impl<'r> ExactSizeIterator for RunesIter<'r> {}
+impl<'r> FusedIterator for RunesIter<'r> {}
+
+/// Iterator over the [RuneRef(s)](crate::RuneRef) of
+/// [Runes](crate::Runes) along with their byte offsets, analogous to
+/// [str::char_indices], see [Runes::rune_indices](crate::Runes::rune_indices).
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::Runes;
+/// let runes = Runes::new("a👌🏽b");
+/// let indices = runes
+///     .rune_indices()
+///     .map(|(offset, rune)| (offset, rune.to_string()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(indices, vec![
+///     (0, "a".to_string()),
+///     (1, "👌🏽".to_string()),
+///     (9, "b".to_string()),
+/// ]);
+/// ```
+#[derive(Clone)]
+pub struct RuneIndices<'r> {
+    bytes: &'r [u8],
+    windows: Windows<'r, usize>,
+}
+
+impl<'r> RuneIndices<'r> {
+    pub(crate) fn new(bytes: &'r [u8], indexes: &'r [usize]) -> RuneIndices<'r> {
+        RuneIndices {
+            bytes,
+            windows: indexes.windows(2),
+        }
+    }
+
+    fn rune_index(&self, window: &[usize]) -> (usize, RuneRef<'r>) {
+        (window[0], RuneRef::new(&self.bytes[window[0]..window[1]]))
+    }
+}
+
+impl<'r> Iterator for RuneIndices<'r> {
+    type Item = (usize, RuneRef<'r>);
+
+    fn next(&mut self) -> Option<(usize, RuneRef<'r>)> {
+        let window = self.windows.next()?;
+        Some(self.rune_index(window))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+}
+
+impl<'r> DoubleEndedIterator for RuneIndices<'r> {
+    fn next_back(&mut self) -> Option<(usize, RuneRef<'r>)> {
+        let window = self.windows.next_back()?;
+        Some(self.rune_index(window))
+    }
+}
+
+impl<'r> ExactSizeIterator for RuneIndices<'r> {}
+impl<'r> FusedIterator for RuneIndices<'r> {}
+
+/// Owning iterator over the [Rune(s)](crate::Rune) of
+/// [Runes](crate::Runes), the yielded [Rune(s)](crate::Rune) sharing
+/// a single [RuneArena](crate::RuneArena) made of the memory owned by
+/// the [Runes](crate::Runes), or of a copy of the memory they borrow.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::{Rune, Runes};
+/// let runes = Runes::new("🇧🇷🇵🇹");
+/// let vec: Vec<Rune> = runes.into_iter().rev().collect();
+/// assert_eq!(vec, vec![Rune::new("🇵🇹"), Rune::new("🇧🇷")]);
+/// ```
+pub struct RunesIntoIter<'g> {
+    arena: Arc<RuneArena>,
+    indexes: Vec<usize>,
+    front: usize,
+    back: usize,
+    _marker: PhantomData<&'g [u8]>,
+}
+
+impl<'g> RunesIntoIter<'g> {
+    pub(crate) fn new(mut runes: Runes<'g>) -> RunesIntoIter<'g> {
+        let back = runes.len();
+        let arena = match runes.buf.take() {
+            Some(buf) => RuneArena::from(buf),
+            None => RuneArena::from_slice(runes.as_bytes()).expect("memory allocation"),
+        };
+        RunesIntoIter {
+            arena: Arc::new(arena),
+            indexes: core::mem::take(&mut runes.indexes),
+            front: 0,
+            back,
+            _marker: PhantomData,
+        }
+    }
+
+    fn rune(&self, index: usize) -> Rune {
+        Rune::from_arena(
+            self.arena.clone(),
+            self.indexes[index]..self.indexes[index + 1],
+        )
+    }
+}
+
+impl<'g> Iterator for RunesIntoIter<'g> {
+    type Item = Rune;
+
+    fn next(&mut self) -> Option<Rune> {
+        if self.front == self.back {
+            return None;
+        }
+        let rune = self.rune(self.front);
+        self.front += 1;
+        Some(rune)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.back - self.front;
+        (length, Some(length))
+    }
+}
+
+impl<'g> DoubleEndedIterator for RunesIntoIter<'g> {
+    fn next_back(&mut self) -> Option<Rune> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.rune(self.back))
+    }
+}
+
+impl<'g> ExactSizeIterator for RunesIntoIter<'g> {}
+impl<'g> FusedIterator for RunesIntoIter<'g> {}
+
+impl<'r, 'g> IntoIterator for &'r Runes<'g> {
+    type IntoIter = RunesIter<'r>;
+    type Item = RuneRef<'r>;
+
+    fn into_iter(self) -> RunesIter<'r> {
+        self.iter()
+    }
+}
+
+impl<'g> IntoIterator for Runes<'g> {
+    type IntoIter = RunesIntoIter<'g>;
+    type Item = Rune;
+
+    fn into_iter(self) -> RunesIntoIter<'g> {
+        RunesIntoIter::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test_iter {
+    use crate::{Result, Rune, Runes};
+
+    #[test]
+    fn test_for_loop_over_borrowed_runes() -> Result<()> {
+        let runes = Runes::new("cafe\u{301}🇧🇷");
+        let mut strings = Vec::<String>::new();
+        for rune in &runes {
+            strings.push(rune.to_string());
+        }
+        assert_eq!(strings, vec!["c", "a", "f", "e\u{301}", "🇧🇷"]);
+        assert_eq!(
+            runes
+                .iter()
+                .rev()
+                .map(|rune| rune.to_string())
+                .collect::<Vec<_>>(),
+            vec!["🇧🇷", "e\u{301}", "f", "a", "c"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rune_indices_match_rune_indexes() -> Result<()> {
+        let runes = Runes::new("👌👌🏻👌🏼👌🏽👌🏾👌🏿");
+        assert_eq!(
+            runes
+                .rune_indices()
+                .map(|(offset, rune)| (offset, rune.len()))
+                .collect::<Vec<_>>(),
+            runes.rune_indexes()
+        );
+        let (offset, rune) = runes.rune_indices().next_back().unwrap();
+        assert_eq!(offset, 36);
+        assert_eq!(rune, "👌🏿");
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_iter_outlives_borrowed_input() -> Result<()> {
+        let input = String::from("❤️‍🔥xyz");
+        let mut iter = Runes::from_str(&input)?.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(Rune::new("z")));
+        let vec = iter.collect::<Vec<Rune>>();
+        drop(input);
+        assert_eq!(
+            vec,
+            vec![
+                Rune::new("❤️‍🔥"),
+                Rune::new("x"),
+                Rune::new("y")
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_iter_reuses_owned_memory() -> Result<()> {
+        let runes = Runes::new("👌🏽é!");
+        let ptr = runes.as_bytes().as_ptr();
+        let vec = runes.into_iter().collect::<Vec<Rune>>();
+        assert_eq!(vec[0].as_ptr(), ptr);
+        assert_eq!(vec[1].as_ptr(), ptr.wrapping_add(8));
+        assert_eq!(vec[2], Rune::new("!"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_iterators() -> Result<()> {
+        let runes = Runes::empty()?;
+        assert_eq!(runes.iter().len(), 0);
+        assert_eq!(runes.rune_indices().next(), None);
+        assert_eq!(runes.into_iter().next(), None);
+        Ok(())
+    }
+}
@@ -1,30 +1,96 @@
+#![cfg_attr(
+    not(any(
+        feature = "std",
+        test
+    )),
+    no_std
+)]
+extern crate alloc;
+
 mod errors;
 #[doc(inline)]
-pub use errors::{Error, Result};
+pub use errors::{Error, ErrorKind, Result};
+
+mod excerpt;
+#[doc(inline)]
+pub use excerpt::{Excerpt, EXCERPT_RADIUS};
+
+mod invalid_region;
+#[doc(inline)]
+pub use invalid_region::InvalidRegion;
 
 mod byte_type;
 #[doc(inline)]
 pub use byte_type::ByteType;
 
+mod byte_map;
+#[doc(inline)]
+pub use byte_map::{ByteInfo, ByteMap, ByteRole};
+
+mod ascii;
+#[doc(inline)]
+pub use ascii::ascii_prefix_len;
+
+mod validator;
+#[doc(inline)]
+pub use validator::{is_valid_utf8, utf8_valid_up_to, Utf8State};
+
+mod allocator;
+#[doc(inline)]
+pub use allocator::{DefaultAllocator, RuneAllocator};
+
+mod rune_buf;
+#[doc(inline)]
+pub use rune_buf::RuneBuf;
+
+mod rune_arena;
+#[doc(inline)]
+pub use rune_arena::RuneArena;
+
 mod rune;
 #[doc(inline)]
-pub use rune::Rune;
+pub use rune::{Rune, RUNE_INLINE_CAPACITY};
+
+mod rune_ref;
+#[doc(inline)]
+pub use rune_ref::RuneRef;
 
 mod runes;
 #[doc(inline)]
 pub use runes::Runes;
 
+mod secret_runes;
+#[doc(inline)]
+pub use secret_runes::SecretRunes;
+
+mod iter;
+#[doc(inline)]
+pub use iter::{RuneIndices, RunesIntoIter, RunesIter};
+
+mod segmenter;
+#[doc(inline)]
+pub use segmenter::Segmenter;
+
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use reader::{RuneReader, DEFAULT_READER_CAPACITY};
+
+#[cfg(feature = "pointer")]
 pub mod pointer;
+#[cfg(not(feature = "pointer"))]
+pub(crate) mod pointer;
 
 #[cfg(feature = "pointer")]
 pub use pointer::{
-    copy, create, destroy, from_display, from_slice, get_byte_at_index,
+    copy, create, destroy, destroy_in, from_display, from_slice, get_byte_at_index,
     get_byte_slice_of, get_valid_utf8_str_of, is_valid_utf8_str_of,
 };
 #[cfg(not(feature = "pointer"))]
 #[allow(unused_imports)]
 pub(crate) use pointer::{
-    copy, create, destroy, from_display, from_slice, get_byte_at_index,
+    copy, create, destroy, destroy_in, from_display, from_slice, get_byte_at_index,
     get_byte_slice_of, get_valid_utf8_str_of, is_valid_utf8_str_of,
 };
 
@@ -35,10 +101,18 @@ mod parts;
 #[doc(inline)]
 pub use parts::RuneParts;
 
+mod grapheme;
+#[doc(inline)]
+pub use grapheme::{GraphemeClusterBreak, GraphemeClusterState};
+
+mod tables;
+
 mod heuristic;
 #[doc(inline)]
 pub use heuristic::{
-    continuation_bytes_location, get_rune_cutoff_at_index, split_at_first_rune,
+    continuation_bytes_location, get_rune_cutoff_at_index,
+    get_rune_cutoff_at_index_with_limit, split_at_first_rune,
+    DEFAULT_MAX_TRAILING_CODE_POINTS,
 };
 
 #[cfg(not(feature = "pointer"))]
@@ -48,4 +122,17 @@ pub mod mem;
 #[cfg(not(feature = "pointer"))]
 pub(crate) use mem::layout;
 #[cfg(feature = "pointer")]
-pub use mem::{layout, MemoryError};
+pub use mem::layout;
+#[cfg(all(
+    feature = "stats",
+    feature = "std"
+))]
+#[doc(inline)]
+pub use mem::thread_allocation_stats;
+#[cfg(feature = "stats")]
+#[doc(inline)]
+pub use mem::{allocation_stats, AllocationStats};
+#[doc(inline)]
+pub use mem::{
+    max_input_size, set_max_input_size, MemoryError, DEFAULT_MAX_INPUT_SIZE,
+};
@@ -1,7 +1,358 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::alloc::Layout;
 use core::fmt::{Debug, Display, Formatter};
+use core::sync::atomic::{AtomicUsize, Ordering};
 
-use crate::Result;
+use crate::{Error, Result};
+
+/// default maximum number of bytes that may be allocated at once, see
+/// [set_max_input_size](crate::set_max_input_size)
+pub const DEFAULT_MAX_INPUT_SIZE: usize = isize::MAX as usize;
+
+static MAX_INPUT_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_INPUT_SIZE);
+
+/// sets the maximum number of bytes that may be allocated at once for
+/// any input, such that larger inputs are rejected with an
+/// [Error::MemoryError](crate::Error::MemoryError) rather than
+/// exhausting memory.
+///
+/// The limit is a single global value: it applies to every thread
+/// and to every user of this crate within the process, including
+/// other libraries, from the moment it is set. It is meant to be set
+/// once at startup by the application rather than changed around
+/// individual calls.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::{max_input_size, set_max_input_size, Error, Runes};
+///
+/// set_max_input_size(16);
+/// assert_eq!(max_input_size(), 16);
+/// assert!(Runes::allocate("sixteen bytes ok").is_ok());
+/// assert!(matches!(
+///     Runes::allocate("seventeen bytes!!"),
+///     Err(Error::MemoryError(_))
+/// ));
+/// ```
+pub fn set_max_input_size(size: usize) {
+    MAX_INPUT_SIZE.store(size, Ordering::Relaxed);
+}
+
+/// maximum number of bytes that may be allocated at once, see
+/// [set_max_input_size](crate::set_max_input_size)
+pub fn max_input_size() -> usize {
+    MAX_INPUT_SIZE.load(Ordering::Relaxed)
+}
+
+/// fails with [MemoryError] if `length` exceeds [max_input_size]
+#[inline]
+pub(crate) fn check_input_size(length: usize) -> Result<()> {
+    let max = max_input_size();
+    if length > max {
+        return Err(input_size_error(length, max));
+    }
+    Ok(())
+}
+
+fn input_size_error(length: usize, max: usize) -> Error {
+    MemoryError::new(
+        format!("{length} bytes exceed the maximum input size of {max} bytes"),
+        length,
+        length,
+    )
+    .into()
+}
+
+/// Snapshot of the allocations made through
+/// `pointer::create` and its variants, which
+/// back every [RuneBuf](crate::RuneBuf), and of their deallocations,
+/// available with the `stats` feature.
+///
+/// Counters only ever grow, such that the difference between two
+/// snapshots, see [AllocationStats::since], tells the allocations made
+/// and released in between.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(all(feature = "stats", feature = "std"))] {
+/// use utf8_rune::{thread_allocation_stats, Runes};
+///
+/// let before = thread_allocation_stats();
+/// let runes = Runes::new("👌🏽❤️‍🔥");
+/// let during = thread_allocation_stats().since(&before);
+/// assert_eq!(during.live_allocations(), 1);
+/// assert_eq!(during.live_bytes(), 21);
+///
+/// drop(runes);
+/// let after = thread_allocation_stats().since(&before);
+/// assert_eq!(after.live_allocations(), 0);
+/// assert_eq!(after.allocations, 1);
+/// # }
+/// ```
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AllocationStats {
+    pub allocations: usize,
+    pub deallocations: usize,
+    pub allocated_bytes: usize,
+    pub deallocated_bytes: usize,
+}
+
+#[cfg(feature = "stats")]
+impl AllocationStats {
+    /// number of allocations not yet deallocated, negative if more
+    /// memory was deallocated than allocated, e.g.: by another thread
+    pub fn live_allocations(&self) -> isize {
+        self.allocations.wrapping_sub(self.deallocations) as isize
+    }
+
+    /// number of bytes not yet deallocated, see
+    /// [AllocationStats::live_allocations]
+    pub fn live_bytes(&self) -> isize {
+        self.allocated_bytes
+            .wrapping_sub(self.deallocated_bytes) as isize
+    }
+
+    /// the allocations and deallocations made after `earlier`
+    pub fn since(&self, earlier: &AllocationStats) -> AllocationStats {
+        AllocationStats {
+            allocations: self.allocations.wrapping_sub(earlier.allocations),
+            deallocations: self
+                .deallocations
+                .wrapping_sub(earlier.deallocations),
+            allocated_bytes: self
+                .allocated_bytes
+                .wrapping_sub(earlier.allocated_bytes),
+            deallocated_bytes: self
+                .deallocated_bytes
+                .wrapping_sub(earlier.deallocated_bytes),
+        }
+    }
+}
+
+#[cfg(feature = "stats")]
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "stats")]
+static DEALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "stats")]
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "stats")]
+static DEALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(all(
+    feature = "stats",
+    feature = "std"
+))]
+std::thread_local! {
+    static THREAD_STATS: core::cell::Cell<AllocationStats> =
+        const { core::cell::Cell::new(AllocationStats {
+            allocations: 0,
+            deallocations: 0,
+            allocated_bytes: 0,
+            deallocated_bytes: 0,
+        }) };
+}
+
+/// snapshot of the allocations made by every thread of the process,
+/// see [AllocationStats](crate::AllocationStats)
+#[cfg(feature = "stats")]
+pub fn allocation_stats() -> AllocationStats {
+    AllocationStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        deallocations: DEALLOCATIONS.load(Ordering::Relaxed),
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        deallocated_bytes: DEALLOCATED_BYTES.load(Ordering::Relaxed),
+    }
+}
+
+/// snapshot of the allocations made by the current thread, unaffected
+/// by other threads such as those of tests running in parallel, see
+/// [AllocationStats](crate::AllocationStats)
+#[cfg(all(
+    feature = "stats",
+    feature = "std"
+))]
+pub fn thread_allocation_stats() -> AllocationStats {
+    THREAD_STATS.with(|stats| stats.get())
+}
+
+/// counts an allocation of `size` bytes when the `stats` feature is
+/// enabled
+#[inline]
+#[allow(unused_variables)]
+pub(crate) fn record_allocation(size: usize) {
+    #[cfg(feature = "stats")]
+    {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+        #[cfg(feature = "std")]
+        THREAD_STATS.with(|stats| {
+            let mut current = stats.get();
+            current.allocations += 1;
+            current.allocated_bytes += size;
+            stats.set(current);
+        });
+    }
+}
+
+/// counts a deallocation of `size` bytes when the `stats` feature is
+/// enabled
+#[inline]
+#[allow(unused_variables)]
+pub(crate) fn record_deallocation(size: usize) {
+    #[cfg(feature = "stats")]
+    {
+        DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        DEALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+        #[cfg(feature = "std")]
+        THREAD_STATS.with(|stats| {
+            let mut current = stats.get();
+            current.deallocations += 1;
+            current.deallocated_bytes += size;
+            stats.set(current);
+        });
+    }
+}
+
+/// overwrites `bytes` with zeros through volatile writes, which the
+/// compiler may not elide, available with the `zeroize` feature
+#[cfg(feature = "zeroize")]
+pub(crate) fn zeroize(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+    core::sync::atomic::compiler_fence(Ordering::SeqCst);
+}
+
+/// Growable buffer of input bytes which, with the `zeroize` feature,
+/// overwrites its memory with zeros whenever it is cleared, moves to a
+/// larger allocation or is dropped.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ScratchBuffer {
+    bytes: Vec<u8>,
+}
+
+impl ScratchBuffer {
+    pub(crate) fn with_capacity(capacity: usize) -> ScratchBuffer {
+        ScratchBuffer {
+            bytes: Vec::with_capacity(capacity),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn zeroed(length: usize) -> ScratchBuffer {
+        ScratchBuffer {
+            bytes: alloc::vec![0; length],
+        }
+    }
+
+    pub(crate) fn extend_from_slice(&mut self, bytes: &[u8]) {
+        #[cfg(feature = "zeroize")]
+        if self.bytes.capacity() - self.bytes.len() < bytes.len() {
+            let capacity =
+                (self.bytes.len() + bytes.len()).max(2 * self.bytes.capacity());
+            let mut grown = Vec::with_capacity(capacity);
+            grown.extend_from_slice(&self.bytes);
+            zeroize(&mut self.bytes);
+            self.bytes = grown;
+        }
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub(crate) fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    pub(crate) fn push_str(&mut self, s: &str) {
+        self.extend_from_slice(s.as_bytes());
+    }
+
+    pub(crate) fn clear(&mut self) {
+        #[cfg(feature = "zeroize")]
+        zeroize(&mut self.bytes);
+        self.bytes.clear();
+    }
+}
+
+impl From<Vec<u8>> for ScratchBuffer {
+    fn from(bytes: Vec<u8>) -> ScratchBuffer {
+        ScratchBuffer { bytes }
+    }
+}
+
+impl core::ops::Deref for ScratchBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl core::ops::DerefMut for ScratchBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ScratchBuffer {
+    fn drop(&mut self) {
+        zeroize(&mut self.bytes);
+    }
+}
+
+/// [Write](core::fmt::Write) into a [ScratchBuffer] which fails as
+/// soon as the bytes written would exceed `max`
+struct BoundedWriter {
+    bytes: ScratchBuffer,
+    max: usize,
+    exceeded: Option<usize>,
+}
+
+impl core::fmt::Write for BoundedWriter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let length = self.bytes.len().saturating_add(s.len());
+        if length > self.max {
+            self.exceeded = Some(length);
+            return Err(core::fmt::Error);
+        }
+        self.bytes.push_str(s);
+        Ok(())
+    }
+}
+
+/// calls `f` with the bytes of the string representation of `input`,
+/// which are zeroized afterwards with the `zeroize` feature.
+///
+/// Fails with [MemoryError] as soon as the string representation
+/// exceeds [max_input_size] rather than formatting it whole.
+pub(crate) fn with_display_bytes<T: Display, R>(
+    input: T,
+    f: impl FnOnce(&[u8]) -> Result<R>,
+) -> Result<R> {
+    f(&display_bytes(input, max_input_size())?)
+}
+
+/// the bytes of the string representation of `input` if they do not
+/// exceed `max`
+fn display_bytes<T: Display>(input: T, max: usize) -> Result<ScratchBuffer> {
+    let mut writer = BoundedWriter {
+        bytes: ScratchBuffer::default(),
+        max,
+        exceeded: None,
+    };
+    if core::fmt::write(&mut writer, format_args!("{input}")).is_err() {
+        let length = writer
+            .exceeded
+            .expect("a Display implementation returned an error unexpectedly");
+        return Err(input_size_error(length, max));
+    }
+    Ok(writer.bytes)
+}
 
 #[inline]
 pub fn layout(requested_size: usize) -> Result<Layout> {
@@ -21,7 +372,7 @@ pub struct MemoryError {
     pub message: String,
 }
 
-impl std::error::Error for MemoryError {}
+impl core::error::Error for MemoryError {}
 
 impl MemoryError {
     pub fn new<T: Display>(
@@ -37,8 +388,23 @@ impl MemoryError {
         }
     }
 
+    /// error of a [RuneArena](crate::RuneArena) with `available_size`
+    /// bytes left which cannot fit `requested_size` more bytes
+    pub fn arena_exhausted(
+        requested_size: usize,
+        available_size: usize,
+    ) -> MemoryError {
+        MemoryError::new(
+            format!(
+                "arena exhausted: {requested_size} bytes requested but only {available_size} bytes available"
+            ),
+            requested_size,
+            available_size,
+        )
+    }
+
     pub fn from_layout_error(
-        e: std::alloc::LayoutError,
+        e: core::alloc::LayoutError,
         requested_size: usize,
         actual_size: usize,
     ) -> MemoryError {
@@ -87,3 +453,75 @@ impl Display for MemoryError {
         write!(f, "MemoryError: {message}")
     }
 }
+
+#[cfg(test)]
+mod test_mem {
+    use super::display_bytes;
+    use crate::{pointer, Error};
+    #[cfg(all(
+        feature = "stats",
+        feature = "std"
+    ))]
+    use crate::{thread_allocation_stats, RuneParts, Runes};
+
+    #[test]
+    fn test_failed_allocation_is_an_error() {
+        let error = pointer::create(isize::MAX as usize).unwrap_err();
+        match error {
+            Error::MemoryError(error) => {
+                assert_eq!(error.requested_size, isize::MAX as usize);
+                assert!(error.message.contains("failed to allocate"), "{error}");
+            },
+            error => panic!("unexpected {error:?}"),
+        }
+    }
+
+    #[test]
+    fn test_display_stops_at_max_input_size() {
+        struct Endless;
+        impl core::fmt::Display for Endless {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                loop {
+                    f.write_str("endless")?;
+                }
+            }
+        }
+        match display_bytes(Endless, 64) {
+            Err(Error::MemoryError(error)) => {
+                assert_eq!(error.requested_size, 70);
+                assert!(error.message.contains("maximum input size of 64"), "{error}");
+            },
+            result => panic!("unexpected {result:?}"),
+        }
+        assert_eq!(&*display_bytes("endless", 7).unwrap(), b"endless");
+    }
+
+    #[cfg(all(
+        feature = "stats",
+        feature = "std"
+    ))]
+    #[test]
+    fn test_segmenting_and_dropping_leaves_no_allocations() -> crate::Result<()> {
+        let before = thread_allocation_stats();
+        {
+            let runes = Runes::new("👨‍👩‍👧‍👦 cafe\u{301}");
+            let clone = runes.clone();
+            let vec = clone.into_iter().collect::<Vec<_>>();
+            let parts = RuneParts::new("👨‍👩‍👧‍👦 cafe\u{301}");
+            let runes = (runes, vec, parts.runes()?, parts.rune_at_index(0)?);
+            let during = thread_allocation_stats().since(&before);
+            assert_eq!(during.live_allocations(), 4);
+            drop(runes);
+        }
+        let after = thread_allocation_stats().since(&before);
+        assert_eq!(after.live_allocations(), 0, "{after:?}");
+        assert_eq!(after.live_bytes(), 0, "{after:?}");
+        assert_eq!(after.allocations, 4);
+
+        core::mem::forget(Runes::new("leaked"));
+        let leaked = thread_allocation_stats().since(&before);
+        assert_eq!(leaked.live_allocations(), 1);
+        assert_eq!(leaked.live_bytes(), 6);
+        Ok(())
+    }
+}
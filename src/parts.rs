@@ -1,14 +1,17 @@
-use std::fmt::{Debug, Display, Formatter};
-use std::marker::PhantomData;
-use crate::pointer::{
-    self,
-};
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+use core::marker::PhantomData;
+
+use crate::runes::segment;
 use crate::{
-    display_error, format_bytes, get_rune_cutoff_at_index, unwrap_indent, Result, Rune, Runes,
-    DEFAULT_INDENT,
+    display_error, format_bytes, get_rune_cutoff_at_index, unwrap_indent, Result, Rune,
+    RuneAllocator, RuneArena, RuneBuf, Runes, DEFAULT_INDENT,
+    DEFAULT_MAX_TRAILING_CODE_POINTS,
 };
 
-
 ///
 /// Represents a memory area with contiguous bytes that serves as
 /// building block for [Runes](crate::Runes) and [Rune](crate::Rune).
@@ -23,146 +26,168 @@ use crate::{
 ///
 /// let runes = parts.into_runes();
 /// assert_eq!(runes.len(), 6);
-/// assert_eq!(runes[0], "👌");
-/// assert_eq!(runes[1], "👌🏻");
-/// assert_eq!(runes[2], "👌🏼");
-/// assert_eq!(runes[3], "👌🏽");
-/// assert_eq!(runes[4], "👌🏾");
-/// assert_eq!(runes[5], "👌🏿");
+/// assert_eq!(&runes[0], "👌");
+/// assert_eq!(&runes[1], "👌🏻");
+/// assert_eq!(&runes[2], "👌🏼");
+/// assert_eq!(&runes[3], "👌🏽");
+/// assert_eq!(&runes[4], "👌🏾");
+/// assert_eq!(&runes[5], "👌🏿");
 ///```
 ///
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct RuneParts {
-    pub ptr: *const u8,
-    pub length: usize,
+    arena: Arc<RuneArena>,
 }
 impl RuneParts {
-    pub fn from_raw_parts(ptr: *const u8, length: usize) -> RuneParts {
-        RuneParts { ptr, length }
+    /// creates RuneParts that take ownership of `length` bytes at `ptr`
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by
+    /// [pointer::create](crate::pointer::create) with the given
+    /// `length` and must not be used or deallocated afterwards as the
+    /// returned RuneParts deallocate it when dropped.
+    #[cfg(feature = "pointer")]
+    pub unsafe fn from_raw_parts(ptr: *mut u8, length: usize) -> RuneParts {
+        RuneParts::from_buf(unsafe { RuneBuf::from_raw_parts(ptr, length) })
+    }
+
+    /// copies the given bytes into newly allocated RuneParts
+    pub fn from_bytes(bytes: &[u8]) -> Result<RuneParts> {
+        Ok(RuneParts::from_buf(RuneBuf::from_slice(bytes)?))
     }
 
+    /// creates RuneParts that take ownership of the given
+    /// [RuneBuf](crate::RuneBuf)
+    pub fn from_buf(buf: RuneBuf) -> RuneParts {
+        RuneParts {
+            arena: Arc::new(RuneArena::from(buf)),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the memory for `input` cannot be allocated.
     pub fn new<T: Display>(input: T) -> RuneParts {
-        RuneParts::allocate(&input)
-            .expect(format!("allocate memory for RuneParts from {input}").as_str())
+        RuneParts::allocate(input).expect("allocate memory for RuneParts")
     }
 
+    /// segments these RuneParts into [Runes](crate::Runes) which take
+    /// ownership of their bytes, see
+    /// [RuneParts::indexes](crate::RuneParts::indexes)
+    ///
+    /// # Panics
+    ///
+    /// Panics if these RuneParts hold invalid UTF-8.
+    ///
+    /// The bytes are copied if [Rune(s)](crate::Rune) obtained from
+    /// [RuneParts::runes](crate::RuneParts::runes) still share them.
     pub fn into_runes<'g>(self) -> Runes<'g> {
-        let ptr = self.ptr;
-        let length = self.length;
-        let indexes = self.indexes().leak();
+        let indexes = self.indexes();
+        let buf = Arc::try_unwrap(self.arena)
+            .map(RuneArena::into_buf)
+            .unwrap_or_else(|arena| arena.buf().clone());
         Runes {
-            ptr,
-            length,
+            ptr: buf.as_ptr(),
+            length: buf.len(),
             indexes,
+            buf: Some(buf),
             _marker: PhantomData,
         }
     }
 
     pub fn allocate<T: Display>(input: T) -> Result<RuneParts> {
-        let input = input.to_string();
-        let (ptr, length) = pointer::from_display(&input)?;
-        Ok(RuneParts { ptr, length })
+        Ok(RuneParts::from_buf(RuneBuf::from_display(input)?))
+    }
+
+    /// allocates RuneParts, as well as the [Rune(s)](crate::Rune)
+    /// obtained from them which do not fit inline, with the given
+    /// [RuneAllocator](crate::RuneAllocator)
+    pub fn allocate_in<T: Display>(
+        input: T,
+        allocator: Arc<dyn RuneAllocator>,
+    ) -> Result<RuneParts> {
+        Ok(RuneParts::from_buf(RuneBuf::from_display_in(input, allocator)?))
     }
 
     pub fn rune(&self) -> Option<Rune> {
         match self.rune_at_index(0) {
             Ok(rune) => Some(rune),
             Err(error) => {
-                display_error(error, self.ptr, self.length);
+                display_error(error, self.buf().as_ptr(), self.buf().len());
                 None
             },
         }
     }
 
+    /// cutoffs of the runes of these RuneParts, where runes are kept
+    /// whole however many code points follow their first code point,
+    /// see [RuneParts::runes](crate::RuneParts::runes) to report runes
+    /// longer than
+    /// [DEFAULT_MAX_TRAILING_CODE_POINTS](crate::DEFAULT_MAX_TRAILING_CODE_POINTS)
+    ///
+    /// # Panics
+    ///
+    /// Panics if these RuneParts hold invalid UTF-8, see
+    /// [Runes::from_buf](crate::Runes::from_buf) to obtain the
+    /// [Error](crate::Error) instead.
     pub fn indexes(&self) -> Vec<usize> {
-        let mut cutoff = 0usize;
-        let mut indexes = vec![cutoff];
-        while cutoff < self.length {
-            match get_rune_cutoff_at_index(self.ptr, self.length, cutoff) {
-                Ok(next) => {
-                    indexes.push(next);
-                    cutoff = next;
-                },
-                Err(_) => break,
-            }
+        match segment(self.buf().as_ptr(), self.len(), usize::MAX) {
+            Ok(indexes) => indexes,
+            Err(error) => panic!(
+                "{:?} at index {} of RuneParts",
+                error.kind(),
+                error.offset().unwrap_or_default()
+            ),
         }
-        indexes
     }
 
     pub fn rune_at_index(&self, index: usize) -> Result<Rune> {
-        let cutoff = get_rune_cutoff_at_index(self.ptr, self.length, index)?;
-        let length = cutoff - index;
-        let ptr = pointer::create(length)?;
-        for offset in index..cutoff {
-            unsafe {
-                ptr.add(offset - index)
-                    .write(self.ptr.add(offset).read());
-            }
-        }
-        Ok(Rune { ptr, length })
+        let cutoff = get_rune_cutoff_at_index(self.buf().as_ptr(), self.len(), index)?;
+        Rune::from_slice_with(
+            &self.buf()[index..cutoff],
+            self.buf().allocator().cloned(),
+        )
     }
 
+    /// segments these RuneParts into [Rune(s)](crate::Rune) which
+    /// share the [RuneArena](crate::RuneArena) holding the bytes of
+    /// these RuneParts rather than each taking an allocation of its own
     pub fn runes(&self) -> Result<Vec<Rune>> {
-        let mut runes = Vec::<Rune>::new();
-        let mut index = 0;
-        while index < self.length {
-            let cutoff = match get_rune_cutoff_at_index(self.ptr, self.length, index) {
-                Ok(cutoff) => cutoff,
-                #[allow(unused_variables)]
-                Err(e) => {
-                    #[cfg(feature = "debug")]
-                    {
-                        eprintln!();
-                        dbg!(e);
-                    }
-                    break;
-                },
-            };
-            let length = cutoff - index;
-            let ptr = pointer::create(length)?;
-            for offset in 0..length {
-                unsafe {
-                    ptr.add(offset)
-                        .write(self.ptr.add(index + offset).read());
-                }
-            }
-            runes.push(Rune { ptr, length });
-            index = cutoff;
-        }
-        Ok(runes)
+        let cutoffs =
+            segment(self.buf().as_ptr(), self.len(), DEFAULT_MAX_TRAILING_CODE_POINTS)?;
+        Ok(cutoffs
+            .windows(2)
+            .map(|window| Rune::from_arena(self.arena.clone(), window[0]..window[1]))
+            .collect())
+    }
+
+    fn buf(&self) -> &RuneBuf {
+        self.arena.buf()
     }
 
     pub fn len(&self) -> usize {
-        self.length
+        self.buf().len()
     }
 
-    pub fn as_str<'g>(&self) -> &'g str {
-        let mut offset = self.length;
-        loop {
-            if let Ok(slice) = std::str::from_utf8(unsafe {
-                std::slice::from_raw_parts(self.ptr, offset)
-            }) {
-                break slice;
-            }
-            if offset > 0 {
-                offset -= 1;
-            } else {
-                break "";
-            }
-        }
+    pub fn as_str(&self) -> &str {
+        let bytes = self.as_bytes();
+        core::str::from_utf8(bytes).unwrap_or_else(|error| {
+            core::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap_or_default()
+        })
     }
 
-    pub fn as_bytes<'g>(&self) -> &'g [u8] {
-        unsafe { std::slice::from_raw_parts(self.ptr, self.length) }
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buf().as_bytes()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.length == 0
+        self.buf().is_empty()
     }
 
     pub fn as_debug(&self, indent: Option<usize>) -> String {
         let indent = unwrap_indent(indent);
-        let length = self.length;
+        let length = self.len();
         format!(
             "RuneParts{{{}}}",
             [
@@ -207,19 +232,19 @@ impl From<&String> for RuneParts {
 }
 
 impl Display for RuneParts {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}", self.as_str())
     }
 }
 impl Debug for RuneParts {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}", self.as_debug(None))
     }
 }
 
 #[cfg(test)]
 mod test_parts {
-    use crate::{Rune, RuneParts};
+    use crate::{Error, Result, Rune, RuneParts};
 
     #[test]
     fn test_rune_at_index_error() {
@@ -284,11 +309,73 @@ mod test_parts {
         assert_eq!(parts.as_bytes(), "👌👌🏻👌🏼👌🏽👌🏾👌🏿".as_bytes());
     }
 
+    #[test]
+    fn test_runes_share_an_arena() -> Result<()> {
+        let parts = RuneParts::new("👌👌🏻e\u{301}!");
+        let runes = parts.runes()?;
+        assert_eq!(runes[0].as_ptr(), parts.as_bytes().as_ptr());
+        drop(parts);
+        assert_eq!(
+            runes,
+            vec![
+                Rune::new("👌"),
+                Rune::new("👌🏻"),
+                Rune::new("e\u{301}"),
+                Rune::new("!"),
+            ]
+        );
+        for pair in runes.windows(2) {
+            assert_eq!(pair[0].as_ptr().wrapping_add(pair[0].len()), pair[1].as_ptr());
+        }
+        let last = runes[3].clone();
+        drop(runes);
+        assert_eq!(last.as_str(), "!");
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_runes_owns_bytes() {
+        let parts = RuneParts::new("👌🏽❤️‍🔥");
+        let copy = parts.clone();
+        let runes = parts.into_runes();
+        drop(copy);
+        assert!(!runes.is_borrowed());
+        assert_eq!(&runes[1], "❤️‍🔥");
+    }
+
+    #[test]
+    fn test_into_runes_while_runes_share_the_arena() -> Result<()> {
+        let parts = RuneParts::new("👌🏽❤️‍🔥");
+        let shared = parts.runes()?;
+        let runes = parts.into_runes();
+        assert_ne!(runes.as_bytes().as_ptr(), shared[0].as_ptr());
+        drop(shared);
+        assert_eq!(&runes[1], "❤️‍🔥");
+        Ok(())
+    }
+
     #[test]
     fn test_rune_indexes() {
         let parts = RuneParts::new("👌👌🏻👌🏼👌🏽👌🏾👌🏿");
         assert_eq!(parts.indexes(), vec![0, 4, 12, 20, 28, 36, 44]);
     }
+
+    #[test]
+    fn test_cluster_too_long() {
+        let input = format!("a{}", "\u{301}".repeat(40));
+        let parts = RuneParts::new(&input);
+        assert!(matches!(parts.runes(), Err(Error::ClusterTooLong(0, 30, _))));
+        assert_eq!(parts.indexes(), vec![0, input.len()]);
+        assert_eq!(parts.into_runes().len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidLeadByte at index 2 of RuneParts")]
+    fn test_indexes_of_invalid_utf8() {
+        RuneParts::from_bytes(b"ok\xFF!")
+            .unwrap()
+            .indexes();
+    }
     #[test]
     fn test_rune_at_index() {
         let parts = RuneParts::new("👌👌🏻👌🏼👌🏽👌🏾👌🏿");
@@ -1,28 +1,55 @@
+//! raw pointer functions underlying [RuneBuf](crate::RuneBuf), only
+//! public with the `pointer` feature
+#![cfg_attr(
+    not(feature = "pointer"),
+    allow(dead_code)
+)]
+use alloc::format;
+use alloc::string::ToString;
 use core::fmt::Display;
 
-use crate::{layout, Result};
+use crate::mem::{check_input_size, record_allocation, record_deallocation};
+use crate::{layout, DefaultAllocator, MemoryError, Result, RuneAllocator};
 
-//TODO: std::alloc => alloc::alloc
 /// allocates a new, zero-initialized, raw pointer (i.e.: `*mut u8`) of N contiguous bytes where N=`length`
 ///
+/// Fails with [Error::MemoryError](crate::Error::MemoryError) if the
+/// allocation fails or if `length` exceeds
+/// [max_input_size](crate::max_input_size).
+///
 /// Example
 ///
 /// ```
+/// # #[cfg(feature = "pointer")] {
 /// use utf8_rune::pointer::{self, from_slice};
 /// let (ptr, length) = from_slice(b"bytes").unwrap();
 /// pointer::destroy(ptr, length).unwrap();
+/// # }
 /// ```
 #[inline]
 pub fn create(length: usize) -> Result<*mut u8> {
+    create_in(length, &DefaultAllocator)
+}
+
+/// equivalent to [create](crate::pointer::create) except that the
+/// memory is allocated by the given [RuneAllocator](crate::RuneAllocator)
+#[inline]
+pub(crate) fn create_in(
+    length: usize,
+    allocator: &dyn RuneAllocator,
+) -> Result<*mut u8> {
+    check_input_size(length)?;
     let layout = layout(length)?;
-    let src = unsafe {
-        let src = std::alloc::alloc_zeroed(layout);
-        if src.is_null() {
-            std::alloc::handle_alloc_error(layout);
-        }
-        src
-    };
-    let src = src;
+    let src = allocator.allocate(layout);
+    if src.is_null() {
+        return Err(MemoryError::new(
+            format!("failed to allocate {} bytes", layout.size()),
+            length,
+            layout.size(),
+        )
+        .into());
+    }
+    record_allocation(layout.size());
     for a in 0..length {
         unsafe {
             src.add(a).write(0);
@@ -38,18 +65,54 @@ pub fn create(length: usize) -> Result<*mut u8> {
 /// Example
 ///
 /// ```
+/// # #[cfg(feature = "pointer")] {
 /// use utf8_rune::pointer::{self, from_display};
 /// let (ptr, length) = from_display("bytes").unwrap();
 /// pointer::destroy(ptr, length).unwrap();
+/// # }
 /// ```
 ///
 #[inline]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn destroy(src: *mut u8, length: usize) -> Result<()> {
+    unsafe { destroy_in(src, length, &DefaultAllocator) }
+}
+
+/// equivalent to [destroy](crate::pointer::destroy) for memory
+/// allocated with the given [RuneAllocator](crate::RuneAllocator), e.g.:
+/// released by [RuneBuf::into_raw_parts](crate::RuneBuf::into_raw_parts)
+///
+/// # Safety
+///
+/// `src` must be null or have been allocated by `allocator` for
+/// `length` bytes, and must not be used or deallocated afterwards.
+///
+/// Example
+///
+/// ```
+/// # #[cfg(feature = "pointer")] {
+/// use std::sync::Arc;
+/// use utf8_rune::pointer::destroy_in;
+/// use utf8_rune::{DefaultAllocator, RuneAllocator, RuneBuf};
+/// let allocator: Arc<dyn RuneAllocator> = Arc::new(DefaultAllocator);
+/// let buf = RuneBuf::from_slice_in(b"bytes", allocator).unwrap();
+/// let (ptr, length, allocator) = buf.into_raw_parts();
+/// let allocator = allocator.unwrap();
+/// unsafe { destroy_in(ptr, length, allocator.as_ref()).unwrap() };
+/// # }
+/// ```
+#[inline]
+pub unsafe fn destroy_in(
+    src: *mut u8,
+    length: usize,
+    allocator: &dyn RuneAllocator,
+) -> Result<()> {
     if !src.is_null() {
         let layout = layout(length)?;
         unsafe {
-            std::alloc::dealloc(src, layout);
+            allocator.deallocate(src, layout);
         }
+        record_deallocation(layout.size());
     }
     Ok(())
 }
@@ -61,15 +124,18 @@ pub fn destroy(src: *mut u8, length: usize) -> Result<()> {
 /// Example
 ///
 /// ```
+/// # #[cfg(feature = "pointer")] {
 /// use utf8_rune::pointer::{self, from_display, get_byte_at_index, copy};
 /// let (ptr, length) = from_display("bytes").unwrap();
 /// let other_ptr = copy(ptr, length).unwrap();
 /// assert_eq!(get_byte_at_index(ptr, 2), b't');
 /// pointer::destroy(ptr, length).unwrap();
 /// pointer::destroy(other_ptr, length).unwrap();
+/// # }
 /// ```
 ///
 #[inline]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn copy(src: *const u8, length: usize) -> Result<*mut u8> {
     let mut index = 0;
     let alloc = create(length)?;
@@ -93,16 +159,16 @@ pub fn copy(src: *const u8, length: usize) -> Result<*mut u8> {
 /// Example
 ///
 /// ```
+/// # #[cfg(feature = "pointer")] {
 /// use utf8_rune::pointer::{self, from_slice, get_byte_at_index};
 /// let (ptr, length) = from_slice(b"bytes").unwrap();
 /// assert_eq!(get_byte_at_index(ptr, 2), b't');
 /// pointer::destroy(ptr, length).unwrap();
+/// # }
 /// ```
 ///
 #[inline]
 pub fn from_slice(bytes: &[u8]) -> Result<(*mut u8, usize)> {
-    let bytes = bytes.to_vec();
-
     let src = create(bytes.len())?;
     let length = bytes.len();
     if length == 0 {
@@ -125,15 +191,17 @@ pub fn from_slice(bytes: &[u8]) -> Result<(*mut u8, usize)> {
 /// Example
 ///
 /// ```
+/// # #[cfg(feature = "pointer")] {
 /// use utf8_rune::pointer::{self, from_display, get_byte_at_index};
 /// let (ptr, length) = from_display("bytes").unwrap();
 /// assert_eq!(get_byte_at_index(ptr, 2), b't');
 /// pointer::destroy(ptr, length).unwrap();
+/// # }
 /// ```
 ///
 #[inline]
 pub fn from_display<T: Display>(input: T) -> Result<(*mut u8, usize)> {
-    Ok(from_slice(input.to_string().as_bytes())?)
+    from_slice(input.to_string().as_bytes())
 }
 
 /// retrieves a byte from contiguous memory area
@@ -141,14 +209,16 @@ pub fn from_display<T: Display>(input: T) -> Result<(*mut u8, usize)> {
 /// Example
 ///
 /// ```
+/// # #[cfg(feature = "pointer")] {
 /// use utf8_rune::pointer::get_byte_at_index;
 /// let ptr = "bytes".as_bytes().as_ptr();
 /// assert_eq!(get_byte_at_index(ptr, 2), b't');
+/// # }
 /// ```
 #[inline]
-pub fn get_byte_at_index<'g>(src: *const u8, index: usize) -> u8 {
-    let byte = unsafe { src.add(index).read() };
-    byte
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn get_byte_at_index(src: *const u8, index: usize) -> u8 {
+    unsafe { src.add(index).read() }
 }
 
 /// retrieves a slice of N bytes from contiguous memory area where `N=count` starting at `index`
@@ -156,14 +226,16 @@ pub fn get_byte_at_index<'g>(src: *const u8, index: usize) -> u8 {
 /// Example
 ///
 /// ```
+/// # #[cfg(feature = "pointer")] {
 /// use utf8_rune::pointer::get_byte_slice_of;
 /// let ptr = "UNICODE".as_bytes().as_ptr();
 /// assert_eq!(get_byte_slice_of(ptr, 2, 3), b"ICO");
+/// # }
 /// ```
 #[inline]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn get_byte_slice_of<'g>(src: *const u8, index: usize, count: usize) -> &'g [u8] {
-    let bytes = unsafe { std::slice::from_raw_parts(src.add(index), count) };
-    bytes
+    unsafe { core::slice::from_raw_parts(src.add(index), count) }
 }
 
 /// retrieves `Some()` valid str slice of N bytes from contiguous
@@ -173,9 +245,11 @@ pub fn get_byte_slice_of<'g>(src: *const u8, index: usize, count: usize) -> &'g
 /// Example
 ///
 /// ```
+/// # #[cfg(feature = "pointer")] {
 /// use utf8_rune::pointer::get_valid_utf8_str_of;
 /// let ptr = "UNICODE".as_bytes().as_ptr();
 /// assert_eq!(get_valid_utf8_str_of(ptr, 2, 3), Some("ICO"));
+/// # }
 /// ```
 #[inline]
 pub fn get_valid_utf8_str_of<'g>(
@@ -183,7 +257,7 @@ pub fn get_valid_utf8_str_of<'g>(
     index: usize,
     count: usize,
 ) -> Option<&'g str> {
-    std::str::from_utf8(get_byte_slice_of(src, index, count)).ok()
+    core::str::from_utf8(get_byte_slice_of(src, index, count)).ok()
 }
 
 /// returns `true` if the sequence of `count` bytes from `index` is a
@@ -192,11 +266,13 @@ pub fn get_valid_utf8_str_of<'g>(
 /// Example
 ///
 /// ```
+/// # #[cfg(feature = "pointer")] {
 /// use utf8_rune::pointer::is_valid_utf8_str_of;
 /// let ptr = "UNICODE".as_bytes().as_ptr();
 /// assert_eq!(is_valid_utf8_str_of(ptr, 2, 3), true);
+/// # }
 /// ```
 #[inline]
-pub fn is_valid_utf8_str_of<'g>(src: *const u8, index: usize, count: usize) -> bool {
+pub fn is_valid_utf8_str_of(src: *const u8, index: usize, count: usize) -> bool {
     get_valid_utf8_str_of(src, index, count).is_some()
 }
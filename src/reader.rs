@@ -0,0 +1,243 @@
+use std::io::{ErrorKind, Read};
+use std::iter::FusedIterator;
+
+use crate::mem::ScratchBuffer;
+use crate::segmenter::Segment;
+use crate::{Error, Result, Rune, Segmenter};
+
+/// default number of bytes requested from the underlying reader at a time
+pub const DEFAULT_READER_CAPACITY: usize = 8 * 1024;
+
+/// Reads [Rune(s)](crate::Rune) from any [std::io::Read] one chunk at
+/// a time, such that inputs need not fit in memory.
+///
+/// Each chunk is fed to a [Segmenter](crate::Segmenter), such that
+/// UTF-8 sequences and runes split across chunks are kept whole and
+/// errors report the absolute byte offset within the stream at which
+/// they occur.
+///
+/// Invalid UTF-8 sequences yield the [Error](crate::Error) which
+/// describes them after which reading resumes past the offending bytes. Runes
+/// exceeding the limit of code points yield an
+/// [Error::ClusterTooLong](crate::Error::ClusterTooLong) and are
+/// skipped. Failures of the underlying reader yield an
+/// [Error::IoError](crate::Error::IoError) after which the bytes
+/// already read are treated as the end of the input.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::{Rune, RuneReader};
+///
+/// let input = "cafe\u{301}👩🏻‍🚒".as_bytes();
+/// let runes = RuneReader::with_capacity(3, input)
+///     .collect::<Result<Vec<Rune>, _>>()
+///     .unwrap();
+/// assert_eq!(runes, vec![
+///     Rune::new("c"),
+///     Rune::new("a"),
+///     Rune::new("f"),
+///     Rune::new("e\u{301}"),
+///     Rune::new("👩🏻‍🚒"),
+/// ]);
+/// ```
+///
+/// ```
+/// use utf8_rune::{Error, RuneReader};
+///
+/// let input: &[u8] = &[b'o', b'k', 0xFF, b'!'];
+/// let mut reader = RuneReader::new(input);
+/// assert_eq!(reader.next().unwrap().unwrap().as_str(), "o");
+/// assert_eq!(reader.next().unwrap().unwrap().as_str(), "k");
+/// assert!(matches!(reader.next(), Some(Err(Error::InvalidLeadByte(2, 0, 1, _)))));
+/// assert_eq!(reader.next().unwrap().unwrap().as_str(), "!");
+/// assert!(reader.next().is_none());
+/// ```
+pub struct RuneReader<R: Read> {
+    reader: R,
+    segmenter: Segmenter,
+    chunk: ScratchBuffer,
+    segments: std::vec::IntoIter<Segment>,
+    offset: usize,
+    read: usize,
+    eof: bool,
+}
+
+impl<R: Read> RuneReader<R> {
+    pub fn new(reader: R) -> RuneReader<R> {
+        RuneReader::with_capacity(DEFAULT_READER_CAPACITY, reader)
+    }
+
+    /// creates a RuneReader which requests `capacity` bytes from
+    /// `reader` at a time
+    pub fn with_capacity(capacity: usize, reader: R) -> RuneReader<R> {
+        RuneReader {
+            reader,
+            segmenter: Segmenter::new(),
+            chunk: ScratchBuffer::zeroed(capacity.max(1)),
+            segments: Vec::new().into_iter(),
+            offset: 0,
+            read: 0,
+            eof: false,
+        }
+    }
+
+    /// sets the maximum number of code points that may follow the
+    /// first code point of each rune, see
+    /// [get_rune_cutoff_at_index_with_limit](crate::get_rune_cutoff_at_index_with_limit).
+    pub fn with_limit(mut self, max_trailing: usize) -> RuneReader<R> {
+        self.segmenter = self.segmenter.with_limit(max_trailing);
+        self
+    }
+
+    /// absolute byte offset within the stream up to which runes and
+    /// errors have been yielded
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for RuneReader<R> {
+    type Item = Result<Rune>;
+
+    fn next(&mut self) -> Option<Result<Rune>> {
+        loop {
+            if let Some((offset, segment)) = self.segments.next() {
+                self.offset = offset;
+                return Some(segment);
+            }
+            if self.eof {
+                return None;
+            }
+            match self.reader.read(&mut self.chunk) {
+                Ok(0) => {
+                    self.eof = true;
+                    self.segments = self.segmenter.finish_segments().into_iter();
+                },
+                Ok(count) => {
+                    self.read += count;
+                    self.segments = self
+                        .segmenter
+                        .feed_segments(&self.chunk[..count])
+                        .into_iter();
+                },
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.eof = true;
+                    self.segments = self.segmenter.finish_segments().into_iter();
+                    return Some(Err(Error::IoError(
+                        self.read,
+                        error.kind(),
+                        error.to_string(),
+                    )));
+                },
+            }
+        }
+    }
+}
+
+impl<R: Read> FusedIterator for RuneReader<R> {}
+
+#[cfg(test)]
+mod test_reader {
+    use std::io::{ErrorKind, Read};
+
+    use crate::{Error, Result, Rune, RuneReader, Runes};
+
+    /// reads at most one byte at a time and optionally fails at the end
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        fail: bool,
+    }
+
+    impl<'a> Read for Trickle<'a> {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            match self.bytes.split_first() {
+                Some((byte, rest)) => {
+                    buffer[0] = *byte;
+                    self.bytes = rest;
+                    Ok(1)
+                },
+                None if self.fail => Err(std::io::Error::other("disconnected")),
+                None => Ok(0),
+            }
+        }
+    }
+
+    fn trickle(bytes: &[u8]) -> Trickle<'_> {
+        Trickle { bytes, fail: false }
+    }
+
+    #[test]
+    fn test_matches_runes_across_chunk_boundaries() -> Result<()> {
+        let input = "👩🏻‍🚒👌🏿🧑🏽‍🚒👨‍🚒🌶️🎹💔🔥❤️‍🔥❤️‍🩹cafe\u{301} 🇧🇷🇵🇹1️⃣\u{915}\u{94d}\u{937}\r\n";
+        let expected = Runes::new(input)
+            .iter()
+            .map(|rune| rune.to_rune())
+            .collect::<Result<Vec<Rune>>>()?;
+        for capacity in [1, 2, 3, 5, 7, 64] {
+            let runes = RuneReader::with_capacity(capacity, input.as_bytes())
+                .collect::<Result<Vec<Rune>>>()?;
+            assert_eq!(runes, expected, "capacity {capacity}");
+        }
+        let runes = RuneReader::new(trickle(input.as_bytes()))
+            .collect::<Result<Vec<Rune>>>()?;
+        assert_eq!(runes, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_absolute_offsets_of_errors() {
+        let mut input = "🦅".repeat(5).into_bytes();
+        input.extend([0x80, b'x', 0xE2, 0x80, b'y', 0xF0, 0x9F]);
+        let mut reader = RuneReader::with_capacity(3, trickle(&input));
+        for _ in 0..5 {
+            assert_eq!(reader.next(), Some(Ok(Rune::new("🦅"))));
+        }
+        assert_eq!(reader.offset(), 20);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(Error::UnexpectedContinuationByte(0x80, 20, None, None, _)))
+        ));
+        assert_eq!(reader.next(), Some(Ok(Rune::new("x"))));
+        assert!(matches!(
+            reader.next(),
+            Some(Err(Error::TruncatedSequence(22, 3, 2, _)))
+        ));
+        assert_eq!(reader.next(), Some(Ok(Rune::new("y"))));
+        assert!(matches!(
+            reader.next(),
+            Some(Err(Error::TruncatedSequence(25, 4, 2, _)))
+        ));
+        assert_eq!(reader.next(), None);
+        assert_eq!(reader.offset(), 27);
+    }
+
+    #[test]
+    fn test_io_error_ends_input() {
+        let mut reader = RuneReader::new(Trickle {
+            bytes: "ok".as_bytes(),
+            fail: true,
+        });
+        assert_eq!(reader.next(), Some(Ok(Rune::new("o"))));
+        assert!(matches!(
+            reader.next(),
+            Some(Err(Error::IoError(2, ErrorKind::Other, _)))
+        ));
+        assert_eq!(reader.next(), Some(Ok(Rune::new("k"))));
+        assert_eq!(reader.next(), None);
+    }
+
+    #[test]
+    fn test_cluster_too_long() {
+        let input = format!("ok{}", "\u{301}".repeat(4));
+        let mut reader = RuneReader::with_capacity(2, input.as_bytes()).with_limit(3);
+        assert_eq!(reader.next(), Some(Ok(Rune::new("o"))));
+        assert!(matches!(reader.next(), Some(Err(Error::ClusterTooLong(1, 3, _)))));
+        assert_eq!(reader.next(), None);
+    }
+}
@@ -1,14 +1,23 @@
-use std::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
-use std::fmt::{Debug, Display, Formatter};
-use std::hash::{Hash, Hasher};
-use std::ops::Deref;
-
-use crate::pointer::{self, get_byte_slice_of};
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, Range};
+
+use crate::mem::with_display_bytes;
 use crate::{
-    display_error, format_bytes, get_rune_cutoff_at_index, unwrap_indent, Result,
-    DEFAULT_INDENT,
+    display_error, format_bytes, get_rune_cutoff_at_index_with_limit, unwrap_indent,
+    Error, Result, RuneAllocator, RuneArena, RuneBuf, RuneRef, DEFAULT_INDENT,
+    DEFAULT_MAX_TRAILING_CODE_POINTS,
 };
 
+/// maximum number of bytes of a [Rune] stored inline rather than in
+/// memory allocated on the heap
+pub const RUNE_INLINE_CAPACITY: usize = 15;
+
 /// A Rune represents a single visible UTF-8 character. To handle contiguous bytes as multiple runes consider using [Runes](crate::Runes)
 ///
 /// # Examples
@@ -36,11 +45,41 @@ use crate::{
 /// assert_eq!(rune.as_str(), "👩🏻‍🚒");
 /// assert_eq!(rune.as_bytes(), "👩🏻‍🚒".as_bytes());
 ///```
-
-#[derive(Clone, Copy)]
+///
+/// A Rune owns its bytes, which are stored inline when they fit in
+/// [RUNE_INLINE_CAPACITY](crate::RUNE_INLINE_CAPACITY) bytes, as is
+/// the case of most runes, and otherwise either in a
+/// [RuneBuf](crate::RuneBuf) of its own or as a range of a
+/// [RuneArena](crate::RuneArena) shared with the other runes of the
+/// same segmentation, which are deallocated when the last Rune
+/// referencing them is dropped.
+/// [RuneRef](crate::RuneRef) provides a borrowed view of a rune within
+/// memory owned elsewhere, e.g.: by [Runes](crate::Runes).
+#[derive(Clone)]
 pub struct Rune {
-    pub(crate) ptr: *const u8,
-    pub(crate) length: usize,
+    storage: Storage,
+}
+
+#[derive(Clone)]
+enum Storage {
+    Inline(Inline),
+    Buf(RuneBuf),
+    Arena(Arc<RuneArena>, Range<usize>),
+}
+
+/// bytes of a [Rune] stored inline, zeroized when dropped with the
+/// `zeroize` feature
+#[derive(Clone)]
+struct Inline {
+    length: u8,
+    bytes: [u8; RUNE_INLINE_CAPACITY],
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Inline {
+    fn drop(&mut self) {
+        crate::mem::zeroize(&mut self.bytes);
+    }
 }
 
 impl Default for Rune {
@@ -49,78 +88,168 @@ impl Default for Rune {
     }
 }
 impl Rune {
-    pub fn from_raw_parts(ptr: *const u8, length: usize) -> Rune {
-        Rune { ptr, length }
+    /// creates a Rune that takes ownership of `length` bytes at `ptr`
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by
+    /// [pointer::create](crate::pointer::create) with the given
+    /// `length` and must not be used or deallocated afterwards as the
+    /// returned Rune deallocates it when dropped.
+    #[cfg(feature = "pointer")]
+    pub unsafe fn from_raw_parts(ptr: *mut u8, length: usize) -> Rune {
+        Rune::from_buf(unsafe { RuneBuf::from_raw_parts(ptr, length) })
     }
 
+    /// creates a Rune that takes ownership of the given
+    /// [RuneBuf](crate::RuneBuf) without segmenting its bytes
+    pub fn from_buf(buf: RuneBuf) -> Rune {
+        Rune {
+            storage: Storage::Buf(buf),
+        }
+    }
+
+    /// copies `bytes` inline if they fit in
+    /// [RUNE_INLINE_CAPACITY](crate::RUNE_INLINE_CAPACITY) bytes or
+    /// into a [RuneBuf](crate::RuneBuf) otherwise, without segmenting
+    /// them
+    pub(crate) fn from_slice(bytes: &[u8]) -> Result<Rune> {
+        Rune::from_slice_with(bytes, None)
+    }
+
+    /// equivalent to [Rune::from_slice] except that bytes which do
+    /// not fit inline are allocated with the given allocator, or
+    /// [DefaultAllocator](crate::DefaultAllocator) if `None`
+    pub(crate) fn from_slice_with(
+        bytes: &[u8],
+        allocator: Option<Arc<dyn RuneAllocator>>,
+    ) -> Result<Rune> {
+        if bytes.len() > RUNE_INLINE_CAPACITY {
+            return Ok(Rune::from_buf(RuneBuf::from_slice_with(bytes, allocator)?));
+        }
+        let mut inline = Inline {
+            length: bytes.len() as u8,
+            bytes: [0u8; RUNE_INLINE_CAPACITY],
+        };
+        inline.bytes[..bytes.len()].copy_from_slice(bytes);
+        Ok(Rune {
+            storage: Storage::Inline(inline),
+        })
+    }
+
+    /// creates a Rune made of the bytes within `range` of `arena`, which
+    /// must be within bounds
+    pub(crate) fn from_arena(arena: Arc<RuneArena>, range: Range<usize>) -> Rune {
+        debug_assert!(range.end <= arena.len());
+        Rune {
+            storage: Storage::Arena(arena, range),
+        }
+    }
+
+    /// releases the [RuneBuf](crate::RuneBuf) holding the bytes of this
+    /// Rune, copying them out of their [RuneArena](crate::RuneArena) if
+    /// need be
+    pub fn into_buf(self) -> RuneBuf {
+        match self.storage {
+            Storage::Buf(buf) => buf,
+            _ => RuneBuf::from_slice(self.as_bytes()).expect("memory allocation"),
+        }
+    }
+
+    /// returns `true` if the bytes of this Rune are stored inline
+    /// rather than on the heap
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utf8_rune::Rune;
+    /// assert_eq!(Rune::new("👩🏻‍🚒").is_inline(), true);
+    /// assert_eq!(Rune::new("👨‍👩‍👧‍👦").is_inline(), false);
+    /// ```
+    pub fn is_inline(&self) -> bool {
+        matches!(self.storage, Storage::Inline(..))
+    }
+
+    /// allocates the first rune of `input`, which is kept whole however
+    /// many code points follow its first code point, see
+    /// [Rune::allocate](crate::Rune::allocate) to report runes longer
+    /// than [DEFAULT_MAX_TRAILING_CODE_POINTS](crate::DEFAULT_MAX_TRAILING_CODE_POINTS)
+    /// as [Error::ClusterTooLong](crate::Error::ClusterTooLong).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the memory for `input` cannot be allocated.
     pub fn new<T: Display>(input: T) -> Rune {
-        Rune::allocate(&input)
-            .expect(format!("allocate memory for Rune from {input}").as_str())
+        Rune::allocate_with(input, None, usize::MAX).expect("allocate memory for Rune")
     }
 
     pub fn allocate<T: Display>(input: T) -> Result<Rune> {
-        let (input_ptr, input_length) = pointer::from_display(input)?;
-        match get_rune_cutoff_at_index(input_ptr, input_length, 0) {
-            Ok(length) => {
-                let ptr = pointer::create(length)?;
-                for offset in 0..length {
-                    unsafe {
-                        ptr.add(offset)
-                            .write(input_ptr.add(offset).read());
-                    }
-                }
-                pointer::destroy(input_ptr, input_length)?;
-                Ok(Rune::from_raw_parts(ptr, length))
-            },
-            Err(error) => {
-                display_error(error, input_ptr, input_length);
-                Ok(Rune::default())
-            },
-        }
+        Rune::allocate_with(input, None, DEFAULT_MAX_TRAILING_CODE_POINTS)
+    }
+
+    /// equivalent to [Rune::allocate](crate::Rune::allocate) except
+    /// that runes which do not fit inline are allocated with the given
+    /// [RuneAllocator](crate::RuneAllocator)
+    pub fn allocate_in<T: Display>(
+        input: T,
+        allocator: Arc<dyn RuneAllocator>,
+    ) -> Result<Rune> {
+        Rune::allocate_with(input, Some(allocator), DEFAULT_MAX_TRAILING_CODE_POINTS)
+    }
+
+    fn allocate_with<T: Display>(
+        input: T,
+        allocator: Option<Arc<dyn RuneAllocator>>,
+        max_trailing: usize,
+    ) -> Result<Rune> {
+        with_display_bytes(input, |input| {
+            let (ptr, length) = (input.as_ptr(), input.len());
+            match get_rune_cutoff_at_index_with_limit(ptr, length, 0, max_trailing) {
+                Ok(length) => Rune::from_slice_with(&input[..length], allocator),
+                Err(error @ Error::ClusterTooLong(..)) => Err(error),
+                Err(error) => {
+                    display_error(error, input.as_ptr(), input.len());
+                    Rune::empty()
+                },
+            }
+        })
     }
 
     pub fn empty() -> Result<Rune> {
-        let length = 0;
-        let ptr = pointer::create(length)?;
-        Ok(Rune::from_raw_parts(ptr, length))
+        Rune::from_slice(&[])
     }
 
+    /// copies the rune of `input_length` bytes at `input_ptr` which
+    /// starts at `index`
+    #[cfg(feature = "pointer")]
     pub fn from_ptr_cutoff(
         input_ptr: *const u8,
         input_length: usize,
         index: usize,
     ) -> Result<Rune> {
-        let cutoff = get_rune_cutoff_at_index(input_ptr, input_length, index)?;
-        let length = cutoff - index;
-        let ptr = pointer::create(length)?;
+        let cutoff = crate::get_rune_cutoff_at_index(input_ptr, input_length, index)?;
+        let bytes = crate::pointer::get_byte_slice_of(input_ptr, index, cutoff - index);
+        Rune::from_slice(bytes)
+    }
 
-        for (index, byte) in get_byte_slice_of(input_ptr, index, length)
-            .into_iter()
-            .enumerate()
-        {
-            unsafe { ptr.add(index).write(*byte) }
-        }
-        Ok(Rune { ptr, length })
+    pub fn as_str(&self) -> &str {
+        let bytes = self.as_bytes();
+        core::str::from_utf8(bytes).unwrap_or_else(|error| {
+            core::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap_or_default()
+        })
     }
 
-    pub fn as_str<'g>(&self) -> &'g str {
-        let mut offset = self.length;
-        loop {
-            if let Ok(slice) = std::str::from_utf8(unsafe {
-                std::slice::from_raw_parts(self.ptr, offset)
-            }) {
-                break slice;
-            }
-            if offset > 0 {
-                offset -= 1;
-            } else {
-                break "";
-            }
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.storage {
+            Storage::Inline(inline) => &inline.bytes[..inline.length as usize],
+            Storage::Buf(buf) => buf.as_bytes(),
+            Storage::Arena(arena, range) => &arena.as_bytes()[range.clone()],
         }
     }
 
-    pub fn as_bytes<'g>(&self) -> &'g [u8] {
-        unsafe { std::slice::from_raw_parts(self.ptr, self.length) }
+    /// returns a [RuneRef](crate::RuneRef) borrowing the bytes of this Rune
+    pub fn as_rune_ref(&self) -> RuneRef<'_> {
+        RuneRef::new(self.as_bytes())
     }
 
     pub fn as_debug(&self, indent: Option<usize>) -> String {
@@ -152,12 +281,12 @@ impl From<&String> for Rune {
 }
 
 impl Display for Rune {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}", self.as_str())
     }
 }
 impl Debug for Rune {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}", self.as_debug(None))
     }
 }
@@ -170,12 +299,6 @@ impl Deref for Rune {
     }
 }
 
-// impl Drop for Rune {
-//     fn drop(&mut self) {
-//         pointer::destroy(self.ptr, self.length)
-//     }
-// }
-
 impl PartialEq<Rune> for Rune {
     fn eq(&self, other: &Rune) -> bool {
         self.as_bytes().eq(other.as_bytes())
@@ -185,7 +308,7 @@ impl Eq for Rune {}
 
 impl PartialOrd<Rune> for Rune {
     fn partial_cmp(&self, other: &Rune) -> Option<Ordering> {
-        self.as_bytes().partial_cmp(&other.as_bytes())
+        Some(self.cmp(other))
     }
 }
 impl<'g> PartialOrd<&'g str> for Rune {
@@ -198,12 +321,12 @@ impl<'g> PartialOrd<&'g [u8]> for Rune {
         self.as_bytes().partial_cmp(other)
     }
 }
-impl<'g> PartialOrd<Vec<u8>> for Rune {
+impl PartialOrd<Vec<u8>> for Rune {
     fn partial_cmp(&self, other: &Vec<u8>) -> Option<Ordering> {
         self.as_bytes().to_vec().partial_cmp(other)
     }
 }
-impl<'g> PartialOrd<&Vec<u8>> for Rune {
+impl PartialOrd<&Vec<u8>> for Rune {
     fn partial_cmp(&self, other: &&Vec<u8>) -> Option<Ordering> {
         self.as_bytes().to_vec().partial_cmp(other)
     }
@@ -220,12 +343,12 @@ impl<'g> PartialEq<&'g [u8]> for Rune {
         self.as_bytes().eq(*other)
     }
 }
-impl<'g> PartialEq<Vec<u8>> for Rune {
+impl PartialEq<Vec<u8>> for Rune {
     fn eq(&self, other: &Vec<u8>) -> bool {
         self.as_bytes().to_vec().eq(other)
     }
 }
-impl<'g> PartialEq<&Vec<u8>> for Rune {
+impl PartialEq<&Vec<u8>> for Rune {
     fn eq(&self, other: &&Vec<u8>) -> bool {
         self.as_bytes().to_vec().eq(*other)
     }
@@ -247,7 +370,9 @@ impl Hash for Rune {
 
 #[cfg(test)]
 mod test_rune {
-    use crate::Rune;
+    use std::collections::HashMap;
+
+    use crate::{Error, Rune, RUNE_INLINE_CAPACITY};
 
     #[test]
     fn test_single_rune() {
@@ -288,6 +413,56 @@ mod test_rune {
         assert_eq!(rune.as_bytes(), "👌🏿".as_bytes());
     }
 
+    #[test]
+    fn test_cluster_too_long() {
+        let input = format!("a{}", "\u{301}".repeat(40));
+        assert!(matches!(Rune::allocate(&input), Err(Error::ClusterTooLong(0, 30, _))));
+        assert_eq!(Rune::new(&input).as_str(), input.as_str());
+    }
+
+    #[test]
+    fn test_clone_outlives_original() {
+        let rune = Rune::new("👩🏻‍🚒");
+        let clone = rune.clone();
+        assert_ne!(rune.as_ptr(), clone.as_ptr());
+        drop(rune);
+        assert_eq!(clone.as_str(), "👩🏻‍🚒");
+
+        let runes = [
+            clone.clone(),
+            clone.clone(),
+            Rune::default(),
+        ];
+        drop(clone);
+        assert_eq!(runes[0], runes[1]);
+        assert_eq!(runes[2].as_str(), "");
+    }
+
+    #[test]
+    fn test_inline_storage() {
+        let mut map = HashMap::<Rune, usize>::new();
+        for (index, input) in ["e\u{301}", "👩🏻‍🚒", "👨‍👩‍👧‍👦", ""]
+            .into_iter()
+            .enumerate()
+        {
+            map.insert(Rune::new(input), index);
+        }
+        let inline = Rune::new("👩🏻‍🚒");
+        assert_eq!(inline.len(), RUNE_INLINE_CAPACITY);
+        assert!(inline.is_inline());
+        let start = &inline as *const Rune as usize;
+        let range = start..start + size_of::<Rune>();
+        assert!(range.contains(&(inline.as_ptr() as usize)));
+        assert_eq!(map[&inline], 1);
+
+        let spilled = Rune::new("👨‍👩‍👧‍👦");
+        assert!(!spilled.is_inline());
+        assert_eq!(spilled.clone().into_buf().as_bytes(), "👨‍👩‍👧‍👦".as_bytes());
+        assert_eq!(map[&spilled], 2);
+        assert!(Rune::default().is_inline());
+        assert_eq!(map[&Rune::default()], 3);
+    }
+
     #[test]
     fn test_from_multiple_to_vec() {
         let rune = Rune::new("👌👌🏻👌🏼👌🏽👌🏾👌🏿");
@@ -0,0 +1,132 @@
+use alloc::sync::Arc;
+use core::fmt::{Debug, Formatter};
+use core::ops::Range;
+
+use crate::{MemoryError, Result, RuneAllocator, RuneBuf};
+
+/// Fixed-capacity buffer into which the bytes of many
+/// [Rune(s)](crate::Rune) are copied one after another, such that
+/// segmenting an input takes a single allocation rather than one per
+/// rune.
+///
+/// Runes backed by an arena share it through an
+/// [Arc](alloc::sync::Arc) and the arena is deallocated once the last
+/// of them is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::{Error, RuneArena};
+///
+/// let mut arena = RuneArena::with_capacity(8).unwrap();
+/// assert_eq!(arena.push("👌".as_bytes()).unwrap(), 0..4);
+/// assert_eq!(arena.push("é".as_bytes()).unwrap(), 4..6);
+/// assert_eq!(arena.remaining(), 2);
+/// assert_eq!(arena.get(4..6), Some("é".as_bytes()));
+///
+/// let error = arena.push("🦅".as_bytes()).unwrap_err();
+/// assert!(matches!(error, Error::MemoryError(error) if error.actual_size == 2));
+/// ```
+pub struct RuneArena {
+    buf: RuneBuf,
+    length: usize,
+}
+
+impl RuneArena {
+    /// allocates an arena of `capacity` bytes
+    pub fn with_capacity(capacity: usize) -> Result<RuneArena> {
+        RuneArena::with_capacity_with(capacity, None)
+    }
+
+    /// allocates an arena of `capacity` bytes with the given
+    /// [RuneAllocator](crate::RuneAllocator)
+    pub fn with_capacity_in(
+        capacity: usize,
+        allocator: Arc<dyn RuneAllocator>,
+    ) -> Result<RuneArena> {
+        RuneArena::with_capacity_with(capacity, Some(allocator))
+    }
+
+    pub(crate) fn with_capacity_with(
+        capacity: usize,
+        allocator: Option<Arc<dyn RuneAllocator>>,
+    ) -> Result<RuneArena> {
+        Ok(RuneArena {
+            buf: RuneBuf::zeroed_with(capacity, allocator)?,
+            length: 0,
+        })
+    }
+
+    /// allocates a full arena holding a copy of `bytes`
+    pub fn from_slice(bytes: &[u8]) -> Result<RuneArena> {
+        Ok(RuneArena::from(RuneBuf::from_slice(bytes)?))
+    }
+
+    /// copies `bytes` after the bytes already within the arena and
+    /// returns the range they occupy, or fails with
+    /// [Error::MemoryError](crate::Error::MemoryError) if they do not
+    /// fit in the remaining capacity
+    pub fn push(&mut self, bytes: &[u8]) -> Result<Range<usize>> {
+        let remaining = self.remaining();
+        if bytes.len() > remaining {
+            return Err(MemoryError::arena_exhausted(bytes.len(), remaining).into());
+        }
+        let range = self.length..self.length + bytes.len();
+        self.buf.as_mut_bytes()[range.clone()].copy_from_slice(bytes);
+        self.length = range.end;
+        Ok(range)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// number of bytes pushed onto the arena
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.length
+    }
+
+    /// the bytes pushed onto the arena
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.length]
+    }
+
+    pub fn get(&self, range: Range<usize>) -> Option<&[u8]> {
+        self.as_bytes().get(range)
+    }
+
+    pub(crate) fn buf(&self) -> &RuneBuf {
+        &self.buf
+    }
+
+    pub(crate) fn into_buf(self) -> RuneBuf {
+        self.buf
+    }
+}
+
+/// turns a [RuneBuf](crate::RuneBuf) into a full arena without copying
+impl From<RuneBuf> for RuneArena {
+    fn from(buf: RuneBuf) -> RuneArena {
+        let length = buf.len();
+        RuneArena { buf, length }
+    }
+}
+
+impl Debug for RuneArena {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "RuneArena{{capacity: {}, bytes: {:02x?}}}",
+            self.capacity(),
+            self.as_bytes()
+        )
+    }
+}
@@ -0,0 +1,376 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::heuristic::utf8_error_at_index;
+use crate::mem::{with_display_bytes, ScratchBuffer};
+use crate::pointer::{self};
+use crate::{utf8_valid_up_to, DefaultAllocator, Error, Result, RuneAllocator};
+
+/// Owned, immutable and contiguous buffer of bytes which serves as the
+/// storage of [Rune](crate::Rune), [Runes](crate::Runes) and
+/// [RuneParts](crate::RuneParts).
+///
+/// A RuneBuf allocates its bytes with the same allocator as the
+/// functions of the `pointer` module, or with the
+/// [RuneAllocator](crate::RuneAllocator) given to its `_in`
+/// constructors, deallocates them when dropped and only exposes them
+/// through bounds-checked accessors.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::RuneBuf;
+///
+/// let buf = RuneBuf::from_slice("🦅!".as_bytes()).unwrap();
+/// assert_eq!(buf.len(), 5);
+/// assert_eq!(buf.get_byte_at_index(4), Some(b'!'));
+/// assert_eq!(buf.get_byte_at_index(5), None);
+/// assert_eq!(buf.get_byte_slice_of(0, 4), Some("🦅".as_bytes()));
+/// assert_eq!(buf.get_byte_slice_of(4, 2), None);
+///
+/// let copy = buf.clone();
+/// assert_eq!(String::try_from(buf).unwrap(), "🦅!");
+/// assert_eq!(Vec::from(copy), "🦅!".as_bytes());
+/// ```
+pub struct RuneBuf {
+    ptr: *mut u8,
+    length: usize,
+    allocator: Option<Arc<dyn RuneAllocator>>,
+}
+
+// A RuneBuf uniquely owns the memory behind `ptr` which is only ever
+// mutated through `&mut self`, its allocator is `Send + Sync`
+unsafe impl Send for RuneBuf {}
+unsafe impl Sync for RuneBuf {}
+
+impl RuneBuf {
+    pub fn empty() -> Result<RuneBuf> {
+        RuneBuf::from_slice(&[])
+    }
+
+    /// allocates a RuneBuf of `length` zeroed bytes with the given
+    /// allocator, or [DefaultAllocator](crate::DefaultAllocator) if
+    /// `None`
+    pub(crate) fn zeroed_with(
+        length: usize,
+        allocator: Option<Arc<dyn RuneAllocator>>,
+    ) -> Result<RuneBuf> {
+        let ptr = match &allocator {
+            Some(allocator) => pointer::create_in(length, allocator.as_ref())?,
+            None => pointer::create(length)?,
+        };
+        Ok(RuneBuf {
+            ptr,
+            length,
+            allocator,
+        })
+    }
+
+    /// allocates a RuneBuf holding a copy of `bytes` with the given
+    /// allocator, or [DefaultAllocator](crate::DefaultAllocator) if
+    /// `None`
+    pub(crate) fn from_slice_with(
+        bytes: &[u8],
+        allocator: Option<Arc<dyn RuneAllocator>>,
+    ) -> Result<RuneBuf> {
+        let mut buf = RuneBuf::zeroed_with(bytes.len(), allocator)?;
+        buf.as_mut_bytes().copy_from_slice(bytes);
+        Ok(buf)
+    }
+
+    /// allocates a RuneBuf holding a copy of `bytes`
+    pub fn from_slice(bytes: &[u8]) -> Result<RuneBuf> {
+        RuneBuf::from_slice_with(bytes, None)
+    }
+
+    /// allocates a RuneBuf holding a copy of `bytes` with the given
+    /// [RuneAllocator](crate::RuneAllocator)
+    pub fn from_slice_in(
+        bytes: &[u8],
+        allocator: Arc<dyn RuneAllocator>,
+    ) -> Result<RuneBuf> {
+        RuneBuf::from_slice_with(bytes, Some(allocator))
+    }
+
+    /// allocates a RuneBuf holding the bytes of the string
+    /// representation of `input`
+    pub fn from_display<T: Display>(input: T) -> Result<RuneBuf> {
+        with_display_bytes(input, RuneBuf::from_slice)
+    }
+
+    /// allocates a RuneBuf holding the bytes of the string
+    /// representation of `input` with the given
+    /// [RuneAllocator](crate::RuneAllocator)
+    pub fn from_display_in<T: Display>(
+        input: T,
+        allocator: Arc<dyn RuneAllocator>,
+    ) -> Result<RuneBuf> {
+        with_display_bytes(input, |bytes| RuneBuf::from_slice_in(bytes, allocator))
+    }
+
+    /// the [RuneAllocator](crate::RuneAllocator) of this RuneBuf, or
+    /// `None` for [DefaultAllocator](crate::DefaultAllocator)
+    pub fn allocator(&self) -> Option<&Arc<dyn RuneAllocator>> {
+        self.allocator.as_ref()
+    }
+
+    /// creates a RuneBuf that takes ownership of `length` bytes at `ptr`
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by
+    /// [pointer::create](crate::pointer::create) with the given
+    /// `length` and must not be used or deallocated afterwards as the
+    /// returned RuneBuf deallocates it when dropped.
+    #[cfg(feature = "pointer")]
+    pub unsafe fn from_raw_parts(ptr: *mut u8, length: usize) -> RuneBuf {
+        RuneBuf {
+            ptr,
+            length,
+            allocator: None,
+        }
+    }
+
+    /// releases ownership of the bytes of this RuneBuf along with its
+    /// [allocator](RuneBuf::allocator), the bytes must then be
+    /// deallocated with [pointer::destroy_in](crate::pointer::destroy_in)
+    /// and the returned allocator, or with
+    /// [pointer::destroy](crate::pointer::destroy) when it is `None`
+    #[cfg(feature = "pointer")]
+    pub fn into_raw_parts(self) -> (*mut u8, usize, Option<Arc<dyn RuneAllocator>>) {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        (this.ptr, this.length, this.allocator.take())
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        pointer::get_byte_slice_of(self.ptr, 0, self.length)
+    }
+
+    pub(crate) fn as_mut_bytes(&mut self) -> &mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(self.ptr, self.length) }
+    }
+
+    /// bounds-checked equivalent of
+    /// `pointer::get_byte_at_index`
+    pub fn get_byte_at_index(&self, index: usize) -> Option<u8> {
+        self.as_bytes().get(index).copied()
+    }
+
+    /// bounds-checked equivalent of
+    /// `pointer::get_byte_slice_of`
+    pub fn get_byte_slice_of(&self, index: usize, count: usize) -> Option<&[u8]> {
+        self.as_bytes()
+            .get(index..index.checked_add(count)?)
+    }
+}
+
+impl Clone for RuneBuf {
+    fn clone(&self) -> RuneBuf {
+        RuneBuf::from_slice_with(self.as_bytes(), self.allocator.clone())
+            .expect("memory allocation")
+    }
+}
+
+impl Drop for RuneBuf {
+    fn drop(&mut self) {
+        // `self.ptr` was allocated for `self.length` bytes by
+        // `allocator`, whose layout was already obtained then and
+        // therefore cannot fail here
+        let allocator: &dyn RuneAllocator = match &self.allocator {
+            Some(allocator) => allocator.as_ref(),
+            None => &DefaultAllocator,
+        };
+        #[cfg(feature = "zeroize")]
+        crate::mem::zeroize(unsafe {
+            core::slice::from_raw_parts_mut(self.ptr, self.length)
+        });
+        let _ = unsafe { pointer::destroy_in(self.ptr, self.length, allocator) };
+    }
+}
+
+impl Deref for RuneBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<[u8]> for RuneBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl PartialEq for RuneBuf {
+    fn eq(&self, other: &RuneBuf) -> bool {
+        self.as_bytes().eq(other.as_bytes())
+    }
+}
+impl Eq for RuneBuf {}
+
+impl Hash for RuneBuf {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl Debug for RuneBuf {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "RuneBuf{{{:02x?}}}", self.as_bytes())
+    }
+}
+
+impl TryFrom<&[u8]> for RuneBuf {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<RuneBuf> {
+        RuneBuf::from_slice(bytes)
+    }
+}
+
+impl TryFrom<&str> for RuneBuf {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<RuneBuf> {
+        RuneBuf::from_slice(s.as_bytes())
+    }
+}
+
+impl TryFrom<Vec<u8>> for RuneBuf {
+    type Error = Error;
+
+    fn try_from(bytes: Vec<u8>) -> Result<RuneBuf> {
+        RuneBuf::from_slice(&ScratchBuffer::from(bytes))
+    }
+}
+
+impl TryFrom<Box<[u8]>> for RuneBuf {
+    type Error = Error;
+
+    fn try_from(bytes: Box<[u8]>) -> Result<RuneBuf> {
+        RuneBuf::from_slice(&ScratchBuffer::from(bytes.into_vec()))
+    }
+}
+
+impl TryFrom<String> for RuneBuf {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<RuneBuf> {
+        RuneBuf::from_slice(&ScratchBuffer::from(s.into_bytes()))
+    }
+}
+
+impl From<RuneBuf> for Vec<u8> {
+    fn from(buf: RuneBuf) -> Vec<u8> {
+        buf.as_bytes().to_vec()
+    }
+}
+
+impl From<RuneBuf> for Box<[u8]> {
+    fn from(buf: RuneBuf) -> Box<[u8]> {
+        Box::from(buf.as_bytes())
+    }
+}
+
+/// fails with the [Error](crate::Error) describing the first invalid
+/// UTF-8 sequence of the buffer, if any
+impl TryFrom<RuneBuf> for String {
+    type Error = Error;
+
+    fn try_from(buf: RuneBuf) -> Result<String> {
+        let bytes = buf.as_bytes();
+        let index = utf8_valid_up_to(bytes);
+        if index < bytes.len() {
+            return Err(utf8_error_at_index(bytes.as_ptr(), bytes.len(), index));
+        }
+        Ok(String::from_utf8(bytes.to_vec()).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod test_rune_buf {
+    use crate::{Error, Result, RuneBuf};
+
+    #[test]
+    fn test_conversions() -> Result<()> {
+        let buf = RuneBuf::try_from(String::from("❤️‍🔥"))?;
+        assert_eq!(buf.as_bytes(), "❤️‍🔥".as_bytes());
+        assert_eq!(
+            RuneBuf::try_from(b"\xE2\x9D\xA4".to_vec())?.as_bytes(),
+            "❤".as_bytes()
+        );
+        let boxed: Box<[u8]> = Box::from(&b"ok"[..]);
+        assert_eq!(Box::<[u8]>::from(RuneBuf::try_from(boxed)?), Box::from(&b"ok"[..]));
+        assert_eq!(String::try_from(buf.clone())?, "❤️‍🔥");
+        assert_eq!(RuneBuf::empty()?.len(), 0);
+        assert!(RuneBuf::empty()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_utf8_into_string() -> Result<()> {
+        let buf = RuneBuf::try_from(&b"ok\xED\xA0\x80"[..])?;
+        assert!(matches!(
+            String::try_from(buf),
+            Err(Error::EncodedSurrogate(2, 3, 1, _))
+        ));
+        let buf = RuneBuf::try_from(&b"ok\x80!"[..])?;
+        let error = String::try_from(buf).unwrap_err();
+        assert!(
+            matches!(error, Error::UnexpectedContinuationByte(0x80, 2, _, Some(3), _)),
+            "{error:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bounds_checked_accessors() -> Result<()> {
+        let buf = RuneBuf::try_from("abc")?;
+        assert_eq!(buf.get_byte_at_index(2), Some(b'c'));
+        assert_eq!(buf.get_byte_at_index(3), None);
+        assert_eq!(buf.get_byte_slice_of(1, 2), Some(&b"bc"[..]));
+        assert_eq!(buf.get_byte_slice_of(3, 0), Some(&b""[..]));
+        assert_eq!(buf.get_byte_slice_of(2, 2), None);
+        assert_eq!(buf.get_byte_slice_of(1, usize::MAX), None);
+        Ok(())
+    }
+    #[test]
+    #[cfg(feature = "pointer")]
+    fn test_into_raw_parts_returns_the_allocator() -> Result<()> {
+        use alloc::sync::Arc;
+
+        use crate::pointer::{destroy, destroy_in};
+        use crate::{DefaultAllocator, RuneAllocator};
+
+        let (ptr, length, allocator) = RuneBuf::try_from("abc")?.into_raw_parts();
+        assert_eq!(length, 3);
+        assert!(allocator.is_none());
+        destroy(ptr, length)?;
+
+        let allocator: Arc<dyn RuneAllocator> = Arc::new(DefaultAllocator);
+        let buf = RuneBuf::from_slice_in(b"abc", allocator.clone())?;
+        let (ptr, length, returned) = buf.into_raw_parts();
+        let returned = returned.expect("allocator of RuneBuf");
+        assert!(Arc::ptr_eq(&returned, &allocator));
+        unsafe { destroy_in(ptr, length, returned.as_ref()) }?;
+        Ok(())
+    }
+}
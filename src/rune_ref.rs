@@ -0,0 +1,161 @@
+use alloc::format;
+use alloc::string::String;
+use core::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::{format_bytes, unwrap_indent, Result, Rune, DEFAULT_INDENT};
+
+/// A RuneRef is a borrowed view of a single [Rune](crate::Rune)
+/// within memory owned elsewhere, such as [Runes](crate::Runes) or
+/// another [Rune](crate::Rune).
+///
+/// # Examples
+///
+///```
+/// use utf8_rune::Runes;
+/// let runes = Runes::new("👌🏽❤️");
+/// let rune = runes.get(1).unwrap();
+/// assert_eq!(rune.len(), 6);
+/// assert_eq!(rune.as_str(), "❤️");
+/// assert_eq!(rune.as_bytes(), "❤️".as_bytes());
+///```
+///
+///```
+/// use utf8_rune::Rune;
+/// let rune = Rune::new("👩🏻‍🚒");
+/// let view = rune.as_rune_ref();
+/// assert_eq!(view, rune);
+/// assert_eq!(view.to_rune().unwrap(), rune);
+///```
+#[derive(Clone, Copy)]
+pub struct RuneRef<'g> {
+    pub(crate) bytes: &'g [u8],
+}
+
+impl<'g> RuneRef<'g> {
+    pub(crate) fn new(bytes: &'g [u8]) -> RuneRef<'g> {
+        RuneRef { bytes }
+    }
+
+    pub fn as_str(&self) -> &'g str {
+        core::str::from_utf8(self.bytes).unwrap_or_else(|error| {
+            core::str::from_utf8(&self.bytes[..error.valid_up_to()]).unwrap_or_default()
+        })
+    }
+
+    pub fn as_bytes(&self) -> &'g [u8] {
+        self.bytes
+    }
+
+    /// copies the bytes of this RuneRef into a newly allocated [Rune](crate::Rune)
+    pub fn to_rune(&self) -> Result<Rune> {
+        Rune::from_slice(self.bytes)
+    }
+
+    pub fn as_debug(&self, indent: Option<usize>) -> String {
+        let indent = unwrap_indent(indent);
+        format!(
+            "RuneRef{{{}}}{}",
+            self.as_str(),
+            format_bytes(self.as_bytes(), Some(indent + DEFAULT_INDENT)),
+        )
+    }
+}
+
+impl<'g> Display for RuneRef<'g> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+impl<'g> Debug for RuneRef<'g> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.as_debug(None))
+    }
+}
+
+impl<'g> Deref for RuneRef<'g> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl<'g> PartialEq<RuneRef<'_>> for RuneRef<'g> {
+    fn eq(&self, other: &RuneRef) -> bool {
+        self.bytes.eq(other.bytes)
+    }
+}
+impl<'g> Eq for RuneRef<'g> {}
+
+impl<'g> PartialEq<Rune> for RuneRef<'g> {
+    fn eq(&self, other: &Rune) -> bool {
+        self.bytes.eq(other.as_bytes())
+    }
+}
+impl<'g> PartialEq<RuneRef<'g>> for Rune {
+    fn eq(&self, other: &RuneRef<'g>) -> bool {
+        self.as_bytes().eq(other.bytes)
+    }
+}
+
+impl<'g, 'h> PartialEq<&'h str> for RuneRef<'g> {
+    fn eq(&self, other: &&'h str) -> bool {
+        self.as_str().eq(*other)
+    }
+}
+impl<'g, 'h> PartialEq<&'h [u8]> for RuneRef<'g> {
+    fn eq(&self, other: &&'h [u8]) -> bool {
+        self.bytes.eq(*other)
+    }
+}
+
+impl<'g> PartialOrd<RuneRef<'g>> for RuneRef<'g> {
+    fn partial_cmp(&self, other: &RuneRef<'g>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<'g> Ord for RuneRef<'g> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.cmp(other.bytes)
+    }
+}
+
+impl<'g> Hash for RuneRef<'g> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test_rune_ref {
+    use std::collections::HashSet;
+
+    use crate::{Result, Rune, Runes};
+
+    #[test]
+    fn test_rune_ref_to_rune() -> Result<()> {
+        let runes = Runes::new("👌🏻👌🏼");
+        let first = runes.get(0).unwrap();
+        let second = runes.get(1).unwrap();
+        assert_eq!(first, "👌🏻");
+        assert_eq!(second, "👌🏼");
+        assert_ne!(first, second);
+
+        let owned = first.to_rune()?;
+        assert_eq!(owned, Rune::new("👌🏻"));
+        assert_eq!(owned, first);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rune_ref_hash_matches_rune() {
+        let rune = Rune::new("❤️");
+        let mut set = HashSet::new();
+        set.insert(rune.as_rune_ref());
+        set.insert(rune.as_rune_ref());
+        assert_eq!(set.len(), 1);
+    }
+}
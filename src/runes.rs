@@ -1,9 +1,18 @@
-use std::fmt::{Debug, Display, Formatter};
-use std::marker::PhantomData;
-use std::ops::Index;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt::{Debug, Display, Formatter};
+use core::marker::PhantomData;
+use core::ops::{Index, Range};
 
-use crate::pointer;
-use crate::{get_rune_cutoff_at_index, unwrap_indent, Result, Rune};
+use crate::ascii::push_ascii_cutoffs;
+use crate::mem::{check_input_size, ScratchBuffer};
+use crate::{
+    get_rune_cutoff_at_index_with_limit, pointer, unwrap_indent, InvalidRegion, Result,
+    RuneAllocator, RuneBuf, RuneIndices, RuneRef, RunesIter,
+    DEFAULT_MAX_TRAILING_CODE_POINTS,
+};
 
 /// Represents a slice of bytes which can be automatically parsed into
 /// a sequence of [Rune(s)](crate::Rune)
@@ -47,83 +56,278 @@ use crate::{get_rune_cutoff_at_index, unwrap_indent, Result, Rune};
 ///     (36, 8),
 /// ]);
 /// assert_eq!(runes.len(), 6);
-/// assert_eq!(runes[0], "👌");
-/// assert_eq!(runes[1], "👌🏻");
-/// assert_eq!(runes[2], "👌🏼");
-/// assert_eq!(runes[3], "👌🏽");
-/// assert_eq!(runes[4], "👌🏾");
-/// assert_eq!(runes[5], "👌🏿");
+/// assert_eq!(&runes[0], "👌");
+/// assert_eq!(&runes[1], "👌🏻");
+/// assert_eq!(&runes[2], "👌🏼");
+/// assert_eq!(&runes[3], "👌🏽");
+/// assert_eq!(&runes[4], "👌🏾");
+/// assert_eq!(&runes[5], "👌🏿");
 /// ```
-
-#[derive(Clone)]
+///
+/// Runes either own a copy of their input in a [RuneBuf](crate::RuneBuf),
+/// as when created with [Runes::new](crate::Runes::new) or
+/// [Runes::allocate](crate::Runes::allocate), or borrow it without
+/// copying, as when created with [Runes::from_str](crate::Runes::from_str)
+/// or [Runes::from_bytes](crate::Runes::from_bytes).
 pub struct Runes<'g> {
     pub(crate) ptr: *const u8,
-    pub(crate) indexes: &'g [usize],
+    pub(crate) indexes: Vec<usize>,
     pub(crate) length: usize,
-    pub(crate) _marker: PhantomData<&'g usize>,
+    pub(crate) buf: Option<RuneBuf>,
+    pub(crate) _marker: PhantomData<&'g [u8]>,
 }
+
+// Runes never mutate the memory behind `ptr` which either belongs to
+// `buf` or is borrowed for `'g`
+unsafe impl<'g> Send for Runes<'g> {}
+unsafe impl<'g> Sync for Runes<'g> {}
+
 impl<'g> Default for Runes<'g> {
     fn default() -> Runes<'g> {
         Runes::empty().expect("memory allocation")
     }
 }
 impl<'g> Runes<'g> {
+    /// allocates Runes from `input` whose runes are kept whole however
+    /// many code points follow their first code point, see
+    /// [Runes::allocate](crate::Runes::allocate) to report runes longer
+    /// than [DEFAULT_MAX_TRAILING_CODE_POINTS](crate::DEFAULT_MAX_TRAILING_CODE_POINTS)
+    /// as [Error::ClusterTooLong](crate::Error::ClusterTooLong).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the memory for `input` cannot be allocated.
     pub fn new<T: Display>(input: T) -> Runes<'g> {
-        Runes::allocate(&input)
-            .expect(format!("allocate memory for Runes from {input}").as_str())
+        Runes::allocate_with_limit(input, usize::MAX)
+            .expect("allocate memory for Runes")
     }
 
     pub fn allocate<T: Display>(input: T) -> Result<Runes<'g>> {
-        let input = input.to_string();
-        let (ptr, length) = pointer::from_display(&input)?;
-        let mut cutoff: usize = 0;
-        let mut indexes = vec![cutoff];
-        while cutoff < length {
-            match get_rune_cutoff_at_index(ptr, length, cutoff) {
-                Ok(next) => {
-                    indexes.push(next);
-                    cutoff = next;
+        Runes::allocate_with_limit(input, DEFAULT_MAX_TRAILING_CODE_POINTS)
+    }
+
+    /// allocates [Runes](crate::Runes) whose individual runes may
+    /// contain at most `max_trailing` code points after their
+    /// first code point, see
+    /// [get_rune_cutoff_at_index_with_limit](crate::get_rune_cutoff_at_index_with_limit).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utf8_rune::{Error, Runes};
+    /// let runes = Runes::allocate_with_limit("👨‍👩‍👧‍👦👩🏽‍🚒", 6).unwrap();
+    /// assert_eq!(runes.len(), 2);
+    ///
+    /// let error = Runes::allocate_with_limit("👨‍👩‍👧‍👦👩🏽‍🚒", 3).unwrap_err();
+    /// assert!(matches!(error, Error::ClusterTooLong(0, 3, _)));
+    /// ```
+    pub fn allocate_with_limit<T: Display>(
+        input: T,
+        max_trailing: usize,
+    ) -> Result<Runes<'g>> {
+        Runes::from_buf(RuneBuf::from_display(input)?, max_trailing)
+    }
+
+    /// equivalent to [Runes::allocate](crate::Runes::allocate) except
+    /// that the bytes of `input` are allocated with the given
+    /// [RuneAllocator](crate::RuneAllocator)
+    pub fn allocate_in<T: Display>(
+        input: T,
+        allocator: Arc<dyn RuneAllocator>,
+    ) -> Result<Runes<'g>> {
+        Runes::allocate_with_limit_in(
+            input,
+            DEFAULT_MAX_TRAILING_CODE_POINTS,
+            allocator,
+        )
+    }
+
+    /// equivalent to
+    /// [Runes::allocate_with_limit](crate::Runes::allocate_with_limit)
+    /// except that the bytes of `input` are allocated with the given
+    /// [RuneAllocator](crate::RuneAllocator)
+    pub fn allocate_with_limit_in<T: Display>(
+        input: T,
+        max_trailing: usize,
+        allocator: Arc<dyn RuneAllocator>,
+    ) -> Result<Runes<'g>> {
+        Runes::from_buf(RuneBuf::from_display_in(input, allocator)?, max_trailing)
+    }
+
+    /// allocates [Runes](crate::Runes) from the given bytes replacing
+    /// each maximal subpart of an invalid UTF-8 sequence with
+    /// `U+FFFD REPLACEMENT CHARACTER`, as per the "substitution of
+    /// maximal subparts" practice of the Unicode Standard (§3.9) and
+    /// the WHATWG Encoding Standard, such that the runes after invalid
+    /// bytes are preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utf8_rune::Runes;
+    /// let runes = Runes::from_utf8_lossy(b"caf\xC3 \xF0\x9F\xA6!").unwrap();
+    /// assert_eq!(runes.as_str(), "caf\u{FFFD} \u{FFFD}!");
+    /// assert_eq!(runes.len(), 7);
+    /// assert_eq!(&runes[3], "\u{FFFD}");
+    /// ```
+    pub fn from_utf8_lossy(input: &[u8]) -> Result<Runes<'g>> {
+        check_input_size(input.len())?;
+        let lossy = replace_invalid_utf8(input, |_, _| {});
+        Runes::from_buf(RuneBuf::from_slice(&lossy)?, DEFAULT_MAX_TRAILING_CODE_POINTS)
+    }
+
+    /// equivalent to [Runes::from_utf8_lossy](crate::Runes::from_utf8_lossy)
+    /// except that every span of invalid UTF-8 bytes within `input`
+    /// is also reported as an [InvalidRegion](crate::InvalidRegion)
+    /// whose offsets refer to `input`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utf8_rune::Runes;
+    /// let input = b"\xF0\x9F\xA6\x85 \xFF\xFE \xE2\x9D\xA4\xEF\xB8\x8F \xC3";
+    /// let (runes, regions) = Runes::from_utf8_collecting_errors(input).unwrap();
+    /// assert_eq!(runes.as_str(), "🦅 \u{FFFD}\u{FFFD} ❤️ \u{FFFD}");
+    /// assert_eq!(
+    ///     regions.iter().map(|region| region.range.clone()).collect::<Vec<_>>(),
+    ///     vec![5..7, 15..16]
+    /// );
+    /// ```
+    pub fn from_utf8_collecting_errors(
+        input: &[u8],
+    ) -> Result<(Runes<'g>, Vec<InvalidRegion>)> {
+        check_input_size(input.len())?;
+        let mut regions = Vec::<InvalidRegion>::new();
+        let lossy = replace_invalid_utf8(input, |offset, invalid| {
+            let end = offset + invalid.len();
+            let next_valid_cutoff = Some(end).filter(|end| *end < input.len());
+            match regions.last_mut() {
+                Some(region) if region.range.end == offset => {
+                    region.range.end = end;
+                    region.bytes.extend_from_slice(invalid);
+                    region.next_valid_cutoff = next_valid_cutoff;
                 },
-                Err(_) => break,
+                _ => regions.push(InvalidRegion {
+                    range: offset..end,
+                    bytes: invalid.to_vec(),
+                    previous_valid_cutoff: Some(offset).filter(|offset| *offset > 0),
+                    next_valid_cutoff,
+                }),
             }
-        }
+        });
+        let runes = Runes::from_buf(
+            RuneBuf::from_slice(&lossy)?,
+            DEFAULT_MAX_TRAILING_CODE_POINTS,
+        )?;
+        Ok((runes, regions))
+    }
+
+    /// segments the bytes of the given [RuneBuf](crate::RuneBuf) into
+    /// Runes which take ownership of it, or fails with the
+    /// [Error](crate::Error) describing its first invalid UTF-8
+    /// sequence
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utf8_rune::{RuneBuf, Runes, DEFAULT_MAX_TRAILING_CODE_POINTS};
+    /// let buf = RuneBuf::try_from("👌🏽❤️‍🔥").unwrap();
+    /// let runes = Runes::from_buf(buf, DEFAULT_MAX_TRAILING_CODE_POINTS).unwrap();
+    /// assert_eq!(runes.len(), 2);
+    /// assert_eq!(runes.into_buf().unwrap().len(), 21);
+    /// ```
+    pub fn from_buf(buf: RuneBuf, max_trailing: usize) -> Result<Runes<'g>> {
+        let ptr = buf.as_ptr();
+        let length = buf.len();
+        let indexes = segment(ptr, length, max_trailing)?;
         Ok(Runes {
             ptr,
-            indexes: indexes.leak(),
+            indexes,
             length,
+            buf: Some(buf),
             _marker: PhantomData,
         })
     }
 
-    pub fn empty() -> Result<Runes<'g>> {
-        let length = 0;
-        let ptr = pointer::create(length)?;
+    /// releases the [RuneBuf](crate::RuneBuf) owned by these Runes, or
+    /// `None` if they borrow their input
+    pub fn into_buf(self) -> Option<RuneBuf> {
+        self.buf
+    }
+
+    /// segments the given `&str` in place such that the resulting
+    /// runes are slices of `input` rather than copies of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utf8_rune::Runes;
+    /// let input = String::from("👌🏽❤️‍🔥");
+    /// let runes = Runes::from_str(&input).unwrap();
+    /// assert_eq!(runes.len(), 2);
+    /// assert_eq!(runes.as_bytes().as_ptr(), input.as_ptr());
+    /// assert_eq!(runes.get(1).unwrap().as_bytes().as_ptr(), input[8..].as_ptr());
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(input: &'g str) -> Result<Runes<'g>> {
+        Runes::from_bytes(input.as_bytes())
+    }
+
+    /// segments the given slice of bytes in place such that the
+    /// resulting runes are slices of `input` rather than copies of
+    /// it, or fails with the [Error](crate::Error) describing its
+    /// first invalid UTF-8 sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utf8_rune::Runes;
+    /// let input = "🇧🇷🇵🇹".as_bytes();
+    /// let runes = Runes::from_bytes(input).unwrap();
+    /// assert_eq!(runes.len(), 2);
+    /// assert_eq!(&runes[0], "🇧🇷");
+    /// assert_eq!(&runes[1], "🇵🇹");
+    /// ```
+    pub fn from_bytes(input: &'g [u8]) -> Result<Runes<'g>> {
+        let ptr = input.as_ptr();
+        let length = input.len();
+        check_input_size(length)?;
+        let indexes = segment(ptr, length, DEFAULT_MAX_TRAILING_CODE_POINTS)?;
         Ok(Runes {
             ptr,
+            indexes,
             length,
-            indexes: &[],
+            buf: None,
             _marker: PhantomData,
         })
     }
 
-    pub fn as_str(&self) -> &'g str {
-        let mut offset = self.length;
-        loop {
-            if let Ok(slice) = std::str::from_utf8(unsafe {
-                std::slice::from_raw_parts(self.ptr, offset)
-            }) {
-                break slice;
-            }
-            if offset > 0 {
-                offset -= 1;
-            } else {
-                break "";
-            }
-        }
+    pub fn empty() -> Result<Runes<'g>> {
+        let buf = RuneBuf::empty()?;
+        Ok(Runes {
+            ptr: buf.as_ptr(),
+            length: 0,
+            indexes: vec![0],
+            buf: Some(buf),
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        let bytes = self.as_bytes();
+        core::str::from_utf8(bytes).unwrap_or_else(|error| {
+            core::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap_or_default()
+        })
     }
 
-    pub fn as_bytes(&self) -> &'g [u8] {
-        unsafe { std::slice::from_raw_parts(self.ptr, self.length) }
+    pub fn as_bytes(&self) -> &[u8] {
+        pointer::get_byte_slice_of(self.ptr, 0, self.length)
+    }
+
+    /// returns `true` if these Runes borrow their input rather than
+    /// owning a copy of it
+    pub fn is_borrowed(&self) -> bool {
+        self.buf.is_none()
     }
 
     pub fn is_empty(&self) -> bool {
@@ -131,7 +335,7 @@ impl<'g> Runes<'g> {
     }
 
     pub fn len(&self) -> usize {
-        self.indexes.len() -1
+        self.indexes.len().saturating_sub(1)
     }
 
     pub fn as_debug(&self, indent: Option<usize>) -> String {
@@ -152,47 +356,156 @@ impl<'g> Runes<'g> {
 
     pub fn indexes(&self) -> Vec<usize> {
         let mut indexes = self.indexes.to_vec();
-        if indexes.len() > 0 {
+        if !indexes.is_empty() {
             indexes.pop();
         }
         indexes
     }
 
     pub fn rune_indexes(&self) -> Vec<(usize, usize)> {
-        self.indexes()
-            .into_iter()
-            .map(|index| {
-                let next = get_rune_cutoff_at_index(self.ptr, self.length, index)
-                    .unwrap_or_default();
-                let length = if next >= index {
-                    next - index
-                } else {
-                    0
-                };
-                (index, length)
-            })
-            .filter(|(_, length)| *length > 0)
+        self.indexes
+            .windows(2)
+            .map(|window| (window[0], window[1] - window[0]))
             .collect()
     }
 
-    pub fn get(&self, index: usize) -> Option<Rune> {
-        let indexes = self.rune_indexes();
-        if index >= indexes.len() {
-            None
-        } else {
-            let (index, length) = indexes[index];
-            Some(Rune::from_raw_parts(unsafe { self.ptr.add(index) }, length))
+    pub fn get(&self, index: usize) -> Option<RuneRef<'_>> {
+        let start = *self.indexes.get(index)?;
+        let cutoff = *self.indexes.get(index + 1)?;
+        Some(RuneRef::new(&self.as_bytes()[start..cutoff]))
+    }
+
+    /// returns the [Runes](crate::Runes) within the given range of
+    /// rune indexes, borrowing the memory of `self`, or `None` if the
+    /// range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use utf8_rune::Runes;
+    /// let runes = Runes::new("a👌🏽❤️‍🔥b");
+    /// let middle = runes.get_range(1..3).unwrap();
+    /// assert_eq!(middle.len(), 2);
+    /// assert_eq!(middle.as_str(), "👌🏽❤️‍🔥");
+    /// assert_eq!(&middle[1], "❤️‍🔥");
+    /// assert_eq!(&runes[1..3], "👌🏽❤️‍🔥");
+    ///
+    /// assert!(runes.get_range(2..5).is_none());
+    /// ```
+    pub fn get_range(&self, range: Range<usize>) -> Option<Runes<'_>> {
+        let bytes = self.byte_range(range.clone())?;
+        let offset = bytes.start;
+        Some(Runes {
+            ptr: self.as_bytes()[offset..].as_ptr(),
+            indexes: self.indexes[range.start..=range.end]
+                .iter()
+                .map(|index| index - offset)
+                .collect(),
+            length: bytes.len(),
+            buf: None,
+            _marker: PhantomData,
+        })
+    }
+
+    fn byte_range(&self, range: Range<usize>) -> Option<Range<usize>> {
+        if range.start > range.end {
+            return None;
+        }
+        let start = *self.indexes.get(range.start)?;
+        let end = *self.indexes.get(range.end)?;
+        Some(start..end)
+    }
+
+    pub fn to_vec(&self) -> Vec<RuneRef<'_>> {
+        self.iter().collect()
+    }
+
+    /// returns an iterator over the [RuneRef(s)](crate::RuneRef) of
+    /// these Runes
+    pub fn iter(&self) -> RunesIter<'_> {
+        RunesIter::new(self.as_bytes(), &self.indexes)
+    }
+
+    /// returns an iterator over the [RuneRef(s)](crate::RuneRef) of
+    /// these Runes along with the byte offset at which each of them
+    /// starts
+    pub fn rune_indices(&self) -> RuneIndices<'_> {
+        RuneIndices::new(self.as_bytes(), &self.indexes)
+    }
+}
+/// copies `input` into a buffer allocated once, replacing each maximal
+/// subpart of invalid UTF-8 with `U+FFFD` as
+/// [String::from_utf8_lossy] does, and calls `on_invalid` with the
+/// offset and bytes of each of them
+fn replace_invalid_utf8(
+    input: &[u8],
+    mut on_invalid: impl FnMut(usize, &[u8]),
+) -> ScratchBuffer {
+    let replacement = char::REPLACEMENT_CHARACTER.len_utf8();
+    let capacity = input
+        .utf8_chunks()
+        .map(|chunk| match chunk.invalid() {
+            [] => chunk.valid().len(),
+            _ => chunk.valid().len() + replacement,
+        })
+        .sum();
+    let mut lossy = ScratchBuffer::with_capacity(capacity);
+    let mut offset = 0;
+    for chunk in input.utf8_chunks() {
+        lossy.push_str(chunk.valid());
+        offset += chunk.valid().len();
+        let invalid = chunk.invalid();
+        if !invalid.is_empty() {
+            lossy.push_str("\u{FFFD}");
+            on_invalid(offset, invalid);
+            offset += invalid.len();
+        }
+    }
+    lossy
+}
+
+/// returns the cutoff indexes of every rune between `0` and `length`,
+/// both inclusive, or fails with the [Error](crate::Error) describing
+/// the first invalid UTF-8 sequence or rune longer than `max_trailing`.
+///
+/// Runs of ASCII bytes are skipped by the fast path of
+/// [push_ascii_cutoffs](crate::ascii::push_ascii_cutoffs).
+pub(crate) fn segment(
+    ptr: *const u8,
+    length: usize,
+    max_trailing: usize,
+) -> Result<Vec<usize>> {
+    let mut cutoff: usize = 0;
+    let mut indexes = vec![cutoff];
+    while cutoff < length {
+        let bytes = pointer::get_byte_slice_of(ptr, 0, length);
+        cutoff = push_ascii_cutoffs(bytes, cutoff, &mut indexes);
+        if cutoff == length {
+            break;
         }
+        cutoff =
+            get_rune_cutoff_at_index_with_limit(ptr, length, cutoff, max_trailing)?;
+        indexes.push(cutoff);
     }
+    Ok(indexes)
+}
 
-    pub fn to_vec(&self) -> Vec<Rune> {
-        let mut runes = Vec::<Rune>::new();
-        for (index, length) in self.rune_indexes().into_iter() {
-            runes.push(Rune::from_raw_parts(unsafe { self.ptr.add(index) }, length));
+impl<'g> Clone for Runes<'g> {
+    fn clone(&self) -> Runes<'g> {
+        let buf = self.buf.clone();
+        Runes {
+            ptr: buf
+                .as_ref()
+                .map(RuneBuf::as_ptr)
+                .unwrap_or(self.ptr),
+            indexes: self.indexes.clone(),
+            length: self.length,
+            buf,
+            _marker: PhantomData,
         }
-        runes
     }
 }
+
 impl<'g> From<&str> for Runes<'g> {
     fn from(s: &str) -> Runes<'g> {
         Runes::new(s)
@@ -212,30 +525,51 @@ impl<'g> From<&String> for Runes<'g> {
 }
 
 impl<'g> Display for Runes<'g> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}", self.as_str())
     }
 }
 impl<'g> Debug for Runes<'g> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}", self.as_debug(None))
     }
 }
 impl<'g> Index<usize> for Runes<'g> {
-    type Output = &'g str;
+    type Output = str;
 
-    fn index(&self, index: usize) -> &&'g str {
-        if let Some(rune) = self.get(index) {
-            unsafe { std::mem::transmute::<&&str, &&'g str>(&rune.as_str()) }
-        } else {
-            &""
+    fn index(&self, index: usize) -> &str {
+        match self.get(index) {
+            Some(rune) => rune.as_str(),
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {index}",
+                self.len()
+            ),
+        }
+    }
+}
+
+impl<'g> Index<Range<usize>> for Runes<'g> {
+    type Output = str;
+
+    fn index(&self, range: Range<usize>) -> &str {
+        if range.start > range.end {
+            panic!("rune index starts at {} but ends at {}", range.start, range.end);
+        }
+        match self.byte_range(range.clone()) {
+            Some(bytes) => core::str::from_utf8(&self.as_bytes()[bytes])
+                .expect("valid UTF-8 runes"),
+            None => panic!(
+                "range end index {} out of range for Runes of len {}",
+                range.end,
+                self.len()
+            ),
         }
     }
 }
 
 #[cfg(test)]
 mod test_runes {
-    use crate::{Result, Runes};
+    use crate::{Error, Result, RuneBuf, Runes, DEFAULT_MAX_TRAILING_CODE_POINTS};
 
     #[test]
     fn test_to_vec() -> Result<()> {
@@ -261,6 +595,311 @@ mod test_runes {
         );
         Ok(())
     }
+    #[test]
+    fn test_extended_grapheme_clusters() -> Result<()> {
+        let runes = Runes::new("cafe\u{301} 🇧🇷1️⃣\u{1100}\u{1161}👨‍👩‍👧‍👦");
+        assert_eq!(
+            runes
+                .to_vec()
+                .iter()
+                .map(|rune| rune.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "c",
+                "a",
+                "f",
+                "e\u{301}",
+                " ",
+                "🇧🇷",
+                "1️⃣",
+                "\u{1100}\u{1161}",
+                "👨‍👩‍👧‍👦",
+            ]
+        );
+        Ok(())
+    }
+    #[test]
+    fn test_cluster_too_long() -> Result<()> {
+        let input = format!("a{}", "\u{301}".repeat(40));
+        let runes = Runes::allocate_with_limit(&input, 40)?;
+        assert_eq!(runes.len(), 1);
+        assert_eq!(&runes[0], input.as_str());
+
+        let result = Runes::allocate(&input);
+        assert!(matches!(result, Err(Error::ClusterTooLong(0, 30, _))));
+
+        let runes = Runes::new(&input);
+        assert_eq!(runes.len(), 1);
+        assert_eq!(&runes[0], input.as_str());
+        assert_eq!(Runes::from(input.as_str()).indexes(), vec![0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_borrows_input() -> Result<()> {
+        let input = String::from("falcão🦅");
+        let runes = Runes::from_str(&input)?;
+        assert!(runes.is_borrowed());
+        assert_eq!(runes.as_bytes().as_ptr(), input.as_ptr());
+        assert_eq!(
+            runes
+                .to_vec()
+                .iter()
+                .map(|rune| rune.as_bytes().as_ptr())
+                .collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 6, 7].map(|index| input[index..].as_ptr())
+        );
+
+        let clone = runes.clone();
+        drop(runes);
+        assert!(clone.is_borrowed());
+        assert_eq!(clone.as_str(), input);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_bytes_fails_on_invalid_utf8() {
+        let input = [b'o', b'k', 0xFF, b'!'];
+        assert!(matches!(
+            Runes::from_bytes(&input),
+            Err(Error::InvalidLeadByte(2, 0, 1, _))
+        ));
+        let buf = RuneBuf::try_from(&input[..]).unwrap();
+        assert!(matches!(
+            Runes::from_buf(buf, DEFAULT_MAX_TRAILING_CODE_POINTS),
+            Err(Error::InvalidLeadByte(2, 0, 1, _))
+        ));
+    }
+
+    #[test]
+    fn test_from_bytes_stray_continuation_byte_after_large_input() -> Result<()> {
+        let mut input = "é".repeat(1 << 20).into_bytes();
+        input.push(0x80);
+        input.extend_from_slice(b"tail");
+        let error = Runes::from_bytes(&input).unwrap_err();
+        assert!(
+            matches!(error, Error::UnexpectedContinuationByte(0x80, 2097152, _, _, _)),
+            "{error:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_large_input() -> Result<()> {
+        let input = "falcão🦅❤️‍🔥".repeat(1 << 16);
+        let runes = Runes::from_str(&input)?;
+        assert_eq!(input.len(), 24 << 16);
+        assert_eq!(runes.len(), 8 << 16);
+        assert_eq!(runes.get(runes.len() - 1).unwrap(), "❤️‍🔥");
+        for index in (0..runes.len()).step_by(8) {
+            assert_eq!(runes.get(index).unwrap(), "f");
+            assert_eq!(runes.get(index + 7).unwrap(), "❤️‍🔥");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ascii_runs() -> Result<()> {
+        let line = "The quick brown fox jumps over the lazy dog.";
+        let mut prose = Vec::<String>::new();
+        for _ in 0..3 {
+            prose.extend(line.chars().map(String::from));
+            prose.push(String::from("\r\n"));
+        }
+        let expected = [
+            prose.clone(),
+            [
+                "c",
+                "a",
+                "f",
+                "e\u{301}",
+                " ",
+                "1\u{fe0f}\u{20e3}",
+                "\r\n",
+                "\u{301}",
+            ]
+            .map(String::from)
+            .to_vec(),
+            prose,
+        ]
+        .concat();
+        let input = expected.concat();
+        let runes = Runes::from_str(&input)?;
+        assert_eq!(
+            runes
+                .iter()
+                .map(|rune| rune.as_str())
+                .collect::<Vec<&str>>(),
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty() -> Result<()> {
+        for runes in [
+            Runes::empty()?,
+            Runes::new(""),
+            Runes::from_str("")?,
+        ] {
+            assert_eq!(runes.len(), 0);
+            assert!(runes.is_empty());
+            assert!(runes.get(0).is_none());
+            assert!(runes.to_vec().is_empty());
+            assert!(runes.rune_indexes().is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_utf8_lossy_maximal_subparts() -> Result<()> {
+        // Table 3-8 of the Unicode Standard, §3.9
+        let input = b"\x61\xF1\x80\x80\xE1\x80\xC2\x62\x80\x63\x80\xBF\x64";
+        let runes = Runes::from_utf8_lossy(input)?;
+        assert!(!runes.is_borrowed());
+        assert_eq!(
+            runes
+                .iter()
+                .map(|rune| rune.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "a", "\u{FFFD}", "\u{FFFD}", "\u{FFFD}", "b", "\u{FFFD}", "c",
+                "\u{FFFD}", "\u{FFFD}", "d",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_utf8_lossy_keeps_runes_after_invalid_bytes() -> Result<()> {
+        let mut input = b"\xFF".to_vec();
+        input.extend("\u{301}👩🏻‍🚒".as_bytes());
+        input.push(0x80);
+        input.extend("❤️‍🔥".as_bytes());
+        let runes = Runes::from_utf8_lossy(&input)?;
+        assert_eq!(runes.len(), 4);
+        assert_eq!(&runes[0], "\u{FFFD}\u{301}");
+        assert_eq!(&runes[1], "👩🏻‍🚒");
+        assert_eq!(&runes[2], "\u{FFFD}");
+        assert_eq!(&runes[3], "❤️‍🔥");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_utf8_collecting_errors() -> Result<()> {
+        let mut input = "👌🏽".as_bytes().to_vec();
+        input.extend([0x80, 0x80, 0xBF]);
+        input.extend("falcão".as_bytes());
+        input.push(0xFF);
+        input.extend("🦅".as_bytes());
+        input.extend([0xF0, 0x9F]);
+        let (runes, regions) = Runes::from_utf8_collecting_errors(&input)?;
+        assert_eq!(
+            runes.as_str(),
+            "👌🏽\u{FFFD}\u{FFFD}\u{FFFD}falcão\u{FFFD}🦅\u{FFFD}"
+        );
+        assert_eq!(
+            regions
+                .iter()
+                .map(|region| region.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "invalid UTF-8 bytes [0x80, 0x80, 0xbf] at 8..11",
+                "invalid UTF-8 bytes [0xff] at 18..19",
+                "invalid UTF-8 bytes [0xf0, 0x9f] at 23..25",
+            ]
+        );
+        assert_eq!(
+            regions
+                .iter()
+                .map(|region| (region.previous_valid_cutoff, region.next_valid_cutoff))
+                .collect::<Vec<_>>(),
+            vec![
+                (Some(8), Some(11)),
+                (Some(18), Some(19)),
+                (Some(23), None)
+            ]
+        );
+        assert_eq!(
+            regions
+                .iter()
+                .map(|region| region.len())
+                .sum::<usize>(),
+            6
+        );
+
+        let mut input = "é".repeat(1 << 20).into_bytes();
+        input.push(0x80);
+        input.extend_from_slice(b"tail");
+        let (runes, regions) = Runes::from_utf8_collecting_errors(&input)?;
+        assert_eq!(runes.len(), (1 << 20) + 5);
+        assert_eq!(regions[0].range, (2 << 20)..(2 << 20) + 1);
+
+        let (runes, regions) = Runes::from_utf8_collecting_errors(b"valid")?;
+        assert_eq!(runes.len(), 5);
+        assert!(regions.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone_owned() -> Result<()> {
+        let runes = Runes::new("❤️‍🔥👌🏿");
+        let clone = runes.clone();
+        assert!(!clone.is_borrowed());
+        assert_ne!(clone.as_bytes().as_ptr(), runes.as_bytes().as_ptr());
+        drop(runes);
+        assert_eq!(clone.get(0).unwrap(), "❤️‍🔥");
+        assert_eq!(clone.get(1).unwrap(), "👌🏿");
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_outlives_temporaries() -> Result<()> {
+        let runes = Runes::new("👌🏿❤️‍🔥");
+        let first: &str = &runes[0];
+        let second: &str = &runes[1];
+        assert_eq!(first, "👌🏿");
+        assert_eq!(second, "❤️‍🔥");
+        assert_eq!(&runes[0..2], "👌🏿❤️‍🔥");
+        assert_eq!(&runes[1..1], "");
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn test_index_out_of_bounds() {
+        let runes = Runes::new("👌🏿❤️‍🔥");
+        let _ = &runes[2];
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 3 out of range for Runes of len 2")]
+    fn test_index_range_out_of_bounds() {
+        let runes = Runes::new("👌🏿❤️‍🔥");
+        let _ = &runes[1..3];
+    }
+
+    #[test]
+    fn test_get_range() -> Result<()> {
+        let runes = Runes::new("cafe\u{301} 🇧🇷!");
+        let word = runes.get_range(0..4).unwrap();
+        assert!(word.is_borrowed());
+        assert_eq!(word.as_str(), "cafe\u{301}");
+        assert_eq!(word.rune_indexes(), vec![(0, 1), (1, 1), (2, 1), (3, 3)]);
+
+        let flags = runes.get_range(5..7).unwrap();
+        let flag = flags.get_range(0..1).unwrap();
+        assert_eq!(flag.len(), 1);
+        assert_eq!(&flag[0], "🇧🇷");
+
+        assert_eq!(runes.get_range(7..7).unwrap().len(), 0);
+        assert!(runes.get_range(7..8).is_none());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = runes.get_range(3..2);
+        assert!(reversed.is_none());
+        Ok(())
+    }
+
     #[test]
     fn test_length() -> Result<()> {
         let runes = Runes::new("👌👌🏻👌🏼👌🏽👌🏾👌🏿");
@@ -278,12 +917,12 @@ mod test_runes {
                 (36, 8),
             ]
         );
-        assert_eq!(runes[0], "👌");
-        assert_eq!(runes[1], "👌🏻");
-        assert_eq!(runes[2], "👌🏼");
-        assert_eq!(runes[3], "👌🏽");
-        assert_eq!(runes[4], "👌🏾");
-        assert_eq!(runes[5], "👌🏿");
+        assert_eq!(&runes[0], "👌");
+        assert_eq!(&runes[1], "👌🏻");
+        assert_eq!(&runes[2], "👌🏼");
+        assert_eq!(&runes[3], "👌🏽");
+        assert_eq!(&runes[4], "👌🏾");
+        assert_eq!(&runes[5], "👌🏿");
 
         Ok(())
     }
@@ -0,0 +1,157 @@
+use core::fmt::{Debug, Display, Formatter};
+
+use crate::{Error, Result, Runes};
+
+/// Wrapper of [Runes](crate::Runes) holding sensitive text, such as
+/// passwords and passphrases, whose [Debug] and [Display] redact the
+/// content so that it does not end up in logs or error messages.
+///
+/// With the `zeroize` feature the memory owned by the wrapped runes is
+/// overwritten with zeros before being deallocated.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::SecretRunes;
+///
+/// let secret = SecretRunes::new("pässwörd🔑").unwrap();
+/// assert_eq!(secret.len(), 9);
+/// assert_eq!(secret.to_string(), "[REDACTED]");
+/// assert_eq!(format!("{secret:?}"), "SecretRunes{length: 9, [REDACTED]}");
+/// assert_eq!(secret.expose().as_str(), "pässwörd🔑");
+/// ```
+#[derive(Clone, Default)]
+pub struct SecretRunes<'g> {
+    runes: Runes<'g>,
+}
+
+impl<'g> SecretRunes<'g> {
+    /// allocates [Runes](crate::Runes) holding a copy of `input`, see
+    /// [Runes::allocate](crate::Runes::allocate), whose errors carry no
+    /// [excerpt](crate::Error::excerpt) of `input`
+    pub fn new<T: Display>(input: T) -> Result<SecretRunes<'g>> {
+        Ok(SecretRunes {
+            runes: Runes::allocate(input).map_err(Error::redacted)?,
+        })
+    }
+
+    pub fn from_runes(runes: Runes<'g>) -> SecretRunes<'g> {
+        SecretRunes { runes }
+    }
+
+    /// number of runes, which policies such as a minimum password
+    /// length may rely upon without exposing the content
+    pub fn len(&self) -> usize {
+        self.runes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runes.is_empty()
+    }
+
+    /// grants access to the wrapped [Runes](crate::Runes)
+    pub fn expose(&self) -> &Runes<'g> {
+        &self.runes
+    }
+
+    pub fn into_inner(self) -> Runes<'g> {
+        self.runes
+    }
+}
+
+impl<'g> From<Runes<'g>> for SecretRunes<'g> {
+    fn from(runes: Runes<'g>) -> SecretRunes<'g> {
+        SecretRunes::from_runes(runes)
+    }
+}
+
+impl Display for SecretRunes<'_> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+impl Debug for SecretRunes<'_> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "SecretRunes{{length: {}, [REDACTED]}}", self.len())
+    }
+}
+
+#[cfg(test)]
+mod test_secret_runes {
+    use crate::{Error, Result, Runes, SecretRunes};
+
+    #[test]
+    fn test_redacted_formatting() -> Result<()> {
+        let secret = SecretRunes::from(Runes::new("hunter2"));
+        assert_eq!(
+            format!("{secret} {secret:#?}"),
+            "[REDACTED] SecretRunes{length: 7, [REDACTED]}"
+        );
+        assert_eq!(
+            format!("{:?}", SecretRunes::default()),
+            "SecretRunes{length: 0, [REDACTED]}"
+        );
+        assert!(!format!("{:?}", secret.clone()).contains("hunter"));
+        assert_eq!(secret.into_inner().as_str(), "hunter2");
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors_are_redacted() {
+        let input = format!("hunter2{}", "\u{301}".repeat(31));
+        let error = SecretRunes::new(&input).unwrap_err();
+        assert!(matches!(error, Error::ClusterTooLong(6, 30, _)), "{error:?}");
+        assert_eq!(error.excerpt().map(|excerpt| excerpt.len()), Some(0));
+        for output in [error.to_string(), format!("{error:?}")] {
+            assert!(!output.contains("hunter"), "{output}");
+            assert!(!output.contains("0x68, 0x75"), "{output}");
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_memory_is_zeroized_before_deallocation() -> Result<()> {
+        use std::alloc::Layout;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use crate::{DefaultAllocator, RuneAllocator, RuneParts};
+
+        /// counts the deallocations of memory which was not zeroized
+        #[derive(Default)]
+        struct Inspector {
+            dirty: AtomicUsize,
+            deallocations: AtomicUsize,
+        }
+
+        unsafe impl RuneAllocator for Inspector {
+            fn allocate(&self, layout: Layout) -> *mut u8 {
+                DefaultAllocator.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: *mut u8, layout: Layout) {
+                let bytes = unsafe { core::slice::from_raw_parts(ptr, layout.size()) };
+                if bytes.iter().any(|byte| *byte != 0) {
+                    self.dirty.fetch_add(1, Ordering::Relaxed);
+                }
+                self.deallocations.fetch_add(1, Ordering::Relaxed);
+                unsafe { DefaultAllocator.deallocate(ptr, layout) }
+            }
+        }
+
+        let inspector = Arc::new(Inspector::default());
+        let secret = SecretRunes::from(Runes::allocate_in(
+            "correct horse 🐎",
+            inspector.clone(),
+        )?);
+        let parts = RuneParts::allocate_in("👨‍👩‍👧‍👦 battery staple", inspector.clone())?;
+        let runes = parts.runes()?;
+        let family = parts.rune_at_index(0)?;
+        assert_eq!(family.as_str(), "👨‍👩‍👧‍👦");
+        drop((secret, parts, runes, family));
+        assert_eq!(inspector.deallocations.load(Ordering::Relaxed), 3);
+        assert_eq!(inspector.dirty.load(Ordering::Relaxed), 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,325 @@
+use alloc::vec::Vec;
+
+use crate::heuristic::invalid_sequence_error;
+use crate::mem::ScratchBuffer;
+use crate::{
+    Error, Excerpt, GraphemeClusterState, Result, Rune,
+    DEFAULT_MAX_TRAILING_CODE_POINTS,
+};
+
+/// a segmented [Rune](crate::Rune) or [Error](crate::Error) along
+/// with the absolute byte offset at which it ends
+pub(crate) type Segment = (usize, Result<Rune>);
+
+/// Push-based segmentation of UTF-8 input which arrives in fragments
+/// of arbitrary length.
+///
+/// Each call to [feed](Segmenter::feed) yields the
+/// [Rune(s)](crate::Rune) completed by the given bytes whereas the
+/// grapheme cluster state, incomplete UTF-8 sequences and the bytes of
+/// the rune in progress are carried over to the next call.
+/// [finish](Segmenter::finish) yields whatever remains once the input
+/// ends.
+///
+/// Errors report the absolute byte offset within the whole input at
+/// which they occur. Invalid UTF-8 sequences yield the
+/// [Error](crate::Error) which describes them and end the rune in
+/// progress, runes exceeding the limit of code points yield an
+/// [Error::ClusterTooLong](crate::Error::ClusterTooLong) and are
+/// skipped up to their end.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::{Rune, Segmenter};
+///
+/// let bytes = "👋👩🏻‍🚒".as_bytes();
+/// let mut segmenter = Segmenter::new();
+/// let mut runes = Vec::<Rune>::new();
+///
+/// for packet in [&bytes[..6], &bytes[6..13], &bytes[13..]] {
+///     runes.extend(segmenter.feed(packet).map(Result::unwrap));
+/// }
+/// assert_eq!(runes, vec![Rune::new("👋")]);
+///
+/// runes.extend(segmenter.finish().map(Result::unwrap));
+/// assert_eq!(runes, vec![Rune::new("👋"), Rune::new("👩🏻‍🚒")]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Segmenter {
+    state: GraphemeClusterState,
+    cluster: ScratchBuffer,
+    partial: ScratchBuffer,
+    offset: usize,
+    length: usize,
+    trailing: usize,
+    max_trailing: usize,
+    too_long: bool,
+}
+
+/// outcome of decoding the first code point of a slice of bytes
+enum Decoded {
+    Char(char),
+    Invalid(usize),
+    Incomplete,
+}
+
+impl Default for Segmenter {
+    fn default() -> Segmenter {
+        Segmenter::new()
+    }
+}
+
+impl Segmenter {
+    pub fn new() -> Segmenter {
+        Segmenter {
+            state: GraphemeClusterState::default(),
+            cluster: ScratchBuffer::default(),
+            partial: ScratchBuffer::default(),
+            offset: 0,
+            length: 0,
+            trailing: 0,
+            max_trailing: DEFAULT_MAX_TRAILING_CODE_POINTS,
+            too_long: false,
+        }
+    }
+
+    /// sets the maximum number of code points that may follow the
+    /// first code point of each rune, see
+    /// [get_rune_cutoff_at_index_with_limit](crate::get_rune_cutoff_at_index_with_limit).
+    pub fn with_limit(mut self, max_trailing: usize) -> Segmenter {
+        self.max_trailing = max_trailing;
+        self
+    }
+
+    /// absolute byte offset within the input of the rune in progress
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// segments `bytes` as the continuation of the bytes fed so far
+    /// and returns the runes they complete.
+    pub fn feed(&mut self, bytes: &[u8]) -> impl Iterator<Item = Result<Rune>> + use<> {
+        self.feed_segments(bytes)
+            .into_iter()
+            .map(|(_, segment)| segment)
+    }
+
+    /// ends the input and returns the rune in progress, if any,
+    /// along with an error for an incomplete UTF-8 sequence at the end
+    /// of the input. The Segmenter may then be fed a new input.
+    pub fn finish(&mut self) -> impl Iterator<Item = Result<Rune>> + use<> {
+        self.finish_segments()
+            .into_iter()
+            .map(|(_, segment)| segment)
+    }
+
+    pub(crate) fn feed_segments(&mut self, bytes: &[u8]) -> Vec<Segment> {
+        let mut segments = Vec::<Segment>::new();
+        let mut index = 0;
+        while !self.partial.is_empty() && index < bytes.len() {
+            self.partial.push(bytes[index]);
+            index += 1;
+            match decode(&self.partial) {
+                Decoded::Char(c) => {
+                    let partial = core::mem::take(&mut self.partial);
+                    self.push_char(c, &partial, &mut segments);
+                },
+                Decoded::Invalid(count) => {
+                    // the bytes after the invalid sequence were taken
+                    // from `bytes` and are decoded again below
+                    index -= self.partial.len() - count;
+                    let partial = core::mem::take(&mut self.partial);
+                    self.push_invalid(&partial, count, &mut segments);
+                },
+                Decoded::Incomplete => {},
+            }
+        }
+        while index < bytes.len() {
+            let rest = &bytes[index..];
+            match decode(rest) {
+                Decoded::Char(c) => {
+                    let count = c.len_utf8();
+                    self.push_char(c, &rest[..count], &mut segments);
+                    index += count;
+                },
+                Decoded::Invalid(count) => {
+                    self.push_invalid(rest, count, &mut segments);
+                    index += count;
+                },
+                Decoded::Incomplete => {
+                    self.partial.extend_from_slice(rest);
+                    break;
+                },
+            }
+        }
+        segments
+    }
+
+    pub(crate) fn finish_segments(&mut self) -> Vec<Segment> {
+        let mut segments = Vec::<Segment>::new();
+        if !self.partial.is_empty() {
+            let partial = core::mem::take(&mut self.partial);
+            self.push_invalid(&partial, partial.len(), &mut segments);
+        }
+        self.flush(&mut segments);
+        self.state = GraphemeClusterState::default();
+        segments
+    }
+
+    fn push_char(&mut self, c: char, bytes: &[u8], segments: &mut Vec<Segment>) {
+        if self.state.is_boundary_before(c) {
+            self.flush(segments);
+        } else {
+            self.trailing += 1;
+            if self.trailing > self.max_trailing && !self.too_long {
+                self.too_long = true;
+                let excerpt = Excerpt::new(self.offset, &self.cluster);
+                self.cluster.clear();
+                segments.push((
+                    self.offset + self.length,
+                    Err(Error::ClusterTooLong(self.offset, self.max_trailing, excerpt)),
+                ));
+            }
+        }
+        if !self.too_long {
+            self.cluster.extend_from_slice(bytes);
+        }
+        self.length += bytes.len();
+    }
+
+    /// reports the `count` invalid bytes at the start of `window`
+    fn push_invalid(
+        &mut self,
+        window: &[u8],
+        count: usize,
+        segments: &mut Vec<Segment>,
+    ) {
+        self.flush(segments);
+        self.state = GraphemeClusterState::default();
+        let excerpt = Excerpt::new(self.offset, window);
+        let error = invalid_sequence_error(window, self.offset, excerpt);
+        self.offset += count;
+        segments.push((self.offset, Err(error)));
+    }
+
+    /// ends the rune in progress
+    fn flush(&mut self, segments: &mut Vec<Segment>) {
+        if self.length > 0 && !self.too_long {
+            let rune = Rune::from_slice(&self.cluster);
+            segments.push((self.offset + self.length, rune));
+        }
+        self.offset += self.length;
+        self.length = 0;
+        self.trailing = 0;
+        self.too_long = false;
+        self.cluster.clear();
+    }
+}
+
+/// decodes the first code point of the non-empty slice `bytes`
+fn decode(bytes: &[u8]) -> Decoded {
+    let window = &bytes[..bytes.len().min(4)];
+    let valid = match core::str::from_utf8(window) {
+        Ok(valid) => valid,
+        Err(error) if error.valid_up_to() > 0 =>
+            core::str::from_utf8(&window[..error.valid_up_to()]).unwrap_or_default(),
+        Err(error) => {
+            return match error.error_len() {
+                Some(count) => Decoded::Invalid(count),
+                None => Decoded::Incomplete,
+            };
+        },
+    };
+    match valid.chars().next() {
+        Some(c) => Decoded::Char(c),
+        None => Decoded::Incomplete,
+    }
+}
+
+#[cfg(test)]
+mod test_segmenter {
+    use crate::{Error, Result, Rune, Runes, Segmenter};
+
+    #[test]
+    fn test_every_split_matches_runes() -> Result<()> {
+        let input = "👩🏻‍🚒a\u{301}🇧🇷🇵🇹\u{915}\u{94d}\u{937}\r\n1️⃣".as_bytes();
+        let expected = Runes::from_bytes(input)?
+            .iter()
+            .map(|rune| rune.to_rune())
+            .collect::<Result<Vec<Rune>>>()?;
+        for split in 0..=input.len() {
+            let mut segmenter = Segmenter::new();
+            let mut runes = segmenter
+                .feed(&input[..split])
+                .collect::<Result<Vec<Rune>>>()?;
+            runes.extend(
+                segmenter
+                    .feed(&input[split..])
+                    .collect::<Result<Vec<Rune>>>()?,
+            );
+            runes.extend(
+                segmenter
+                    .finish()
+                    .collect::<Result<Vec<Rune>>>()?,
+            );
+            assert_eq!(runes, expected, "split at {split}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_byte_by_byte() -> Result<()> {
+        let input = "❤️‍🔥x".as_bytes();
+        let mut segmenter = Segmenter::new();
+        let mut runes = Vec::<Rune>::new();
+        for byte in input {
+            runes.extend(
+                segmenter
+                    .feed(&[*byte])
+                    .collect::<Result<Vec<Rune>>>()?,
+            );
+        }
+        assert_eq!(runes, vec![Rune::new("❤️‍🔥")]);
+        assert_eq!(segmenter.offset(), 13);
+        runes.extend(
+            segmenter
+                .finish()
+                .collect::<Result<Vec<Rune>>>()?,
+        );
+        assert_eq!(runes, vec![Rune::new("❤️‍🔥"), Rune::new("x")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_sequences_across_fragments() {
+        let mut segmenter = Segmenter::new();
+        let mut results = Vec::<Result<Rune>>::new();
+        for fragment in [
+            &b"a\xE2"[..],
+            &b"\x80b\xF0"[..],
+            &b"\x9F"[..],
+        ] {
+            results.extend(segmenter.feed(fragment));
+        }
+        results.extend(segmenter.finish());
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0], Ok(Rune::new("a")));
+        assert!(matches!(results[1], Err(Error::TruncatedSequence(1, 3, 2, _))));
+        assert_eq!(results[2], Ok(Rune::new("b")));
+        assert!(matches!(results[3], Err(Error::TruncatedSequence(4, 4, 2, _))));
+    }
+
+    #[test]
+    fn test_cluster_too_long_is_skipped() {
+        let input = format!("a{}b", "\u{301}".repeat(5));
+        let mut segmenter = Segmenter::new().with_limit(2);
+        let mut results = segmenter
+            .feed(input.as_bytes())
+            .collect::<Vec<_>>();
+        results.extend(segmenter.finish());
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], Err(Error::ClusterTooLong(0, 2, _))));
+        assert_eq!(results[1], Ok(Rune::new("b")));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::ByteType;
+
+/// State of a deterministic finite automaton which validates UTF-8
+/// one byte at a time based on the [ByteType](crate::ByteType) of each
+/// byte, such that it accepts exactly the inputs which
+/// [core::str::from_utf8] accepts.
+///
+/// The states between [Accept](Utf8State::Accept) and
+/// [Reject](Utf8State::Reject) count the continuation bytes still
+/// expected by the current sequence, those named after a lead byte
+/// restrict the range of the first continuation byte so as to reject
+/// overlong encodings, surrogates and code points above `U+10FFFF`.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::{ByteType, Utf8State};
+///
+/// let mut state = Utf8State::default();
+/// for byte in "é".as_bytes() {
+///     state = state.next(*byte);
+/// }
+/// assert_eq!(state, Utf8State::Accept);
+///
+/// // ED A0 encodes a surrogate
+/// assert_eq!(Utf8State::Accept.next(0xED), Utf8State::ExpectTwoAfterED);
+/// assert_eq!(Utf8State::ExpectTwoAfterED.next(0xA0), Utf8State::Reject);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Utf8State {
+    #[default]
+    Accept,
+    ExpectOne,
+    ExpectTwo,
+    ExpectThree,
+    ExpectTwoAfterE0,
+    ExpectTwoAfterED,
+    ExpectThreeAfterF0,
+    ExpectThreeAfterF4,
+    Reject,
+}
+
+impl Utf8State {
+    /// transitions from this state upon reading `byte`
+    #[inline]
+    pub fn next(self, byte: u8) -> Utf8State {
+        match (self, ByteType::from(byte)) {
+            (Utf8State::Accept, ByteType::Ascii(_)) => Utf8State::Accept,
+            (Utf8State::Accept, ByteType::Two(_)) => Utf8State::ExpectOne,
+            (Utf8State::Accept, ByteType::Three(0xE0)) => Utf8State::ExpectTwoAfterE0,
+            (Utf8State::Accept, ByteType::Three(0xED)) => Utf8State::ExpectTwoAfterED,
+            (Utf8State::Accept, ByteType::Three(_)) => Utf8State::ExpectTwo,
+            (Utf8State::Accept, ByteType::FourOrMore(0xF0)) =>
+                Utf8State::ExpectThreeAfterF0,
+            (Utf8State::Accept, ByteType::FourOrMore(0xF4)) =>
+                Utf8State::ExpectThreeAfterF4,
+            (Utf8State::Accept, ByteType::FourOrMore(_)) => Utf8State::ExpectThree,
+            (Utf8State::ExpectOne, ByteType::Continuation(_)) => Utf8State::Accept,
+            (Utf8State::ExpectTwo, ByteType::Continuation(_)) => Utf8State::ExpectOne,
+            (Utf8State::ExpectThree, ByteType::Continuation(_)) => Utf8State::ExpectTwo,
+            (Utf8State::ExpectTwoAfterE0, ByteType::Continuation(0xA0..=0xBF)) =>
+                Utf8State::ExpectOne,
+            (Utf8State::ExpectTwoAfterED, ByteType::Continuation(0x80..=0x9F)) =>
+                Utf8State::ExpectOne,
+            (Utf8State::ExpectThreeAfterF0, ByteType::Continuation(0x90..=0xBF)) =>
+                Utf8State::ExpectTwo,
+            (Utf8State::ExpectThreeAfterF4, ByteType::Continuation(0x80..=0x8F)) =>
+                Utf8State::ExpectTwo,
+            _ => Utf8State::Reject,
+        }
+    }
+
+    pub fn is_accept(&self) -> bool {
+        matches!(self, Utf8State::Accept)
+    }
+
+    pub fn is_reject(&self) -> bool {
+        matches!(self, Utf8State::Reject)
+    }
+}
+
+/// returns the length of the longest prefix of `bytes` which is valid
+/// UTF-8, equivalent to [core::str::Utf8Error::valid_up_to] or the
+/// length of `bytes` when valid.
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::utf8_valid_up_to;
+/// assert_eq!(utf8_valid_up_to("ok🦅".as_bytes()), 6);
+/// assert_eq!(utf8_valid_up_to(b"ok\xF0\x9F\xA6"), 2);
+/// assert_eq!(utf8_valid_up_to(b"ok\xC0\xAF"), 2);
+/// ```
+pub fn utf8_valid_up_to(bytes: &[u8]) -> usize {
+    let mut state = Utf8State::Accept;
+    let mut valid_up_to = 0;
+    for (index, byte) in bytes.iter().enumerate() {
+        state = state.next(*byte);
+        if state.is_accept() {
+            valid_up_to = index + 1;
+        } else if state.is_reject() {
+            break;
+        }
+    }
+    valid_up_to
+}
+
+/// returns the length of the UTF-8 sequence at the start of `bytes`
+/// by feeding its bytes one at a time to [Utf8State] until it accepts,
+/// or `None` if that sequence is invalid or truncated.
+pub(crate) fn utf8_sequence_len(bytes: &[u8]) -> Option<usize> {
+    let mut state = Utf8State::Accept;
+    for (index, byte) in bytes.iter().enumerate() {
+        state = state.next(*byte);
+        if state.is_accept() {
+            return Some(index + 1);
+        } else if state.is_reject() {
+            return None;
+        }
+    }
+    None
+}
+
+/// returns `true` if `bytes` is valid UTF-8, equivalent to
+/// `std::str::from_utf8(bytes).is_ok()`
+///
+/// # Examples
+///
+/// ```
+/// use utf8_rune::is_valid_utf8;
+/// assert_eq!(is_valid_utf8("❤️‍🔥".as_bytes()), true);
+/// assert_eq!(is_valid_utf8(b"\xED\xA0\x80"), false);
+/// ```
+pub fn is_valid_utf8(bytes: &[u8]) -> bool {
+    utf8_valid_up_to(bytes) == bytes.len()
+}
+
+#[cfg(test)]
+mod test_validator {
+    use crate::validator::utf8_sequence_len;
+    use crate::{is_valid_utf8, utf8_valid_up_to};
+
+    fn assert_matches_std(bytes: &[u8]) {
+        let expected = match std::str::from_utf8(bytes) {
+            Ok(_) => bytes.len(),
+            Err(error) => error.valid_up_to(),
+        };
+        assert_eq!(utf8_valid_up_to(bytes), expected, "{bytes:02x?}");
+        assert_eq!(is_valid_utf8(bytes), expected == bytes.len(), "{bytes:02x?}");
+        let sequence = std::str::from_utf8(&bytes[..expected])
+            .ok()
+            .and_then(|valid| valid.chars().next())
+            .map(char::len_utf8);
+        assert_eq!(utf8_sequence_len(bytes), sequence, "{bytes:02x?}");
+    }
+
+    #[test]
+    fn test_every_two_byte_input_matches_std() {
+        for first in 0..=0xFFu8 {
+            for second in 0..=0xFFu8 {
+                assert_matches_std(&[first, second]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_boundary_three_and_four_byte_inputs_match_std() {
+        let boundaries = [
+            0x00, 0x41, 0x7F, 0x80, 0x8F, 0x90, 0x9F, 0xA0, 0xBF, 0xC0, 0xC1, 0xC2,
+            0xDF, 0xE0, 0xE1, 0xEC, 0xED, 0xEE, 0xEF, 0xF0, 0xF1, 0xF3, 0xF4, 0xF5,
+            0xF8, 0xFF,
+        ];
+        for a in boundaries {
+            for b in boundaries {
+                for c in boundaries {
+                    assert_matches_std(&[a, b, c]);
+                    for d in boundaries {
+                        assert_matches_std(&[a, b, c, d]);
+                        assert_matches_std(&[b'x', a, b, c, d]);
+                    }
+                }
+            }
+        }
+    }
+}